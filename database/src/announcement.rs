@@ -0,0 +1,324 @@
+#[cfg(feature = "graphql")]
+use crate::stubs::{Participant, User};
+use crate::{ApplicationFilter, Result};
+#[cfg(feature = "graphql")]
+use async_graphql::{ComplexObject, Context, Enum, ResultExt, SimpleObject};
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as, QueryBuilder};
+use tracing::instrument;
+use uuid::Uuid;
+
+/// A message broadcast by organizers to a segment of the event's applicants
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct Announcement {
+    /// A unique identifier for the announcement
+    pub id: Uuid,
+    /// The slug of the event the announcement is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+    /// The ID of the organizer that sent the announcement
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub sender_id: i32,
+
+    /// The subject line of the announcement
+    pub subject: String,
+    /// The content of the announcement
+    pub body: String,
+
+    /// When the announcement was sent
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl Announcement {
+    /// The organizer who sent the announcement
+    async fn sender(&self) -> User {
+        User { id: self.sender_id }
+    }
+
+    /// The participants the announcement was sent to
+    #[instrument(name = "Announcement::recipients", skip_all, fields(%self.id))]
+    async fn recipients(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<AnnouncementRecipientStatus>,
+    ) -> async_graphql::Result<Vec<AnnouncementRecipient>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Announcement::list_recipients(self.id, status, db)
+            .await
+            .extend()
+    }
+
+    /// The number of participants the announcement was sent to
+    #[instrument(name = "Announcement::recipient_count", skip_all, fields(%self.id))]
+    async fn recipient_count(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<AnnouncementRecipientStatus>,
+    ) -> async_graphql::Result<i64> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Announcement::count_recipients(self.id, status, db)
+            .await
+            .extend()
+    }
+}
+
+/// Whether an announcement was delivered to a recipient
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[cfg_attr(feature = "graphql", derive(Enum))]
+#[sqlx(rename_all = "lowercase", type_name = "announcement_recipient_status")]
+pub enum AnnouncementRecipientStatus {
+    /// The announcement is waiting to be delivered
+    Pending,
+    /// The announcement was delivered
+    Sent,
+    /// The announcement could not be delivered
+    Failed,
+}
+
+/// A participant an announcement was sent to
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct AnnouncementRecipient {
+    /// The ID of the announcement
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub announcement_id: Uuid,
+    /// The ID of the participant
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub participant_id: i32,
+    /// The slug of the event the announcement is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+    /// The address the announcement was sent to
+    pub address: String,
+
+    /// Whether the announcement was delivered
+    pub status: AnnouncementRecipientStatus,
+    /// Why the announcement could not be delivered
+    pub error: Option<String>,
+    /// When the announcement was delivered
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl AnnouncementRecipient {
+    /// The participant the announcement was sent to
+    async fn participant(&self) -> Participant<'_> {
+        Participant::new(self.participant_id, &self.event)
+    }
+}
+
+impl_queries! {
+    for Announcement;
+
+    /// Get all the announcements for an event, newest first
+    #[instrument(name = "Announcement::all", skip(conn))]
+    pub async fn all(event: &'a str; conn) -> Result<Vec<Announcement>> {
+        let mut conn = conn.acquire().await?;
+        let announcements = query_as!(
+            Announcement,
+            "SELECT * FROM announcements WHERE event = $1 ORDER BY created_at DESC",
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(announcements)
+    }
+
+    /// Get an announcement by its ID
+    #[instrument(name = "Announcement::find", skip(conn))]
+    pub async fn find(event: &'a str, id: Uuid; conn) -> Result<Option<Announcement>> {
+        let mut conn = conn.acquire().await?;
+        let announcement = query_as!(
+            Announcement,
+            "SELECT * FROM announcements WHERE id = $1 AND event = $2",
+            id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(announcement)
+    }
+
+    /// Count the participants matching the filter that an announcement can be sent to
+    #[instrument(name = "Announcement::count_segment", skip(conn))]
+    pub async fn count_segment(event: &'a str, filter: &'a ApplicationFilter; conn) -> Result<i64> {
+        let mut builder = QueryBuilder::new(
+            "SELECT count(*) FROM applications \
+            INNER JOIN emails ON emails.participant_id = applications.participant_id \
+            WHERE applications.event = ",
+        );
+        builder.push_bind(event);
+        filter.push_conditions(&mut builder);
+
+        let mut conn = conn.acquire().await?;
+        let (count,) = builder
+            .build_query_as::<(i64,)>()
+            .fetch_one(&mut *conn)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Create an announcement, recording every participant matching the filter as a recipient
+    #[instrument(name = "Announcement::create", skip(conn))]
+    pub async fn create(
+        event: &'a str,
+        sender_id: i32,
+        subject: &'a str,
+        body: &'a str,
+        filter: &'a ApplicationFilter;
+        conn
+    ) -> Result<Announcement> {
+        let mut conn = conn.acquire().await?;
+        let announcement = query_as!(
+            Announcement,
+            r#"
+            INSERT INTO announcements (event, sender_id, subject, body)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            event,
+            sender_id,
+            subject,
+            body,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let mut builder = QueryBuilder::new(
+            "INSERT INTO announcement_recipients (announcement_id, participant_id, address) \
+            SELECT ",
+        );
+        builder.push_bind(announcement.id);
+        builder.push(
+            ", applications.participant_id, emails.address FROM applications \
+            INNER JOIN emails ON emails.participant_id = applications.participant_id \
+            WHERE applications.event = ",
+        );
+        builder.push_bind(event);
+        filter.push_conditions(&mut builder);
+        builder.build().execute(&mut *conn).await?;
+
+        Ok(announcement)
+    }
+
+    /// Get the recipients of an announcement, optionally only those with the status
+    #[instrument(name = "Announcement::list_recipients", skip(conn))]
+    pub async fn list_recipients(
+        id: Uuid,
+        status: Option<AnnouncementRecipientStatus>;
+        conn
+    ) -> Result<Vec<AnnouncementRecipient>> {
+        let mut conn = conn.acquire().await?;
+        let recipients = query_as!(
+            AnnouncementRecipient,
+            r#"
+            SELECT
+                announcement_id, participant_id, announcements.event, address,
+                status as "status: AnnouncementRecipientStatus", error, sent_at
+            FROM announcement_recipients
+            INNER JOIN announcements ON announcements.id = announcement_recipients.announcement_id
+            WHERE announcement_id = $1 AND ($2::announcement_recipient_status IS NULL OR status = $2)
+            ORDER BY participant_id
+            "#,
+            id,
+            status as Option<AnnouncementRecipientStatus>,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(recipients)
+    }
+
+    /// Count the recipients of an announcement, optionally only those with the status
+    #[instrument(name = "Announcement::count_recipients", skip(conn))]
+    pub async fn count_recipients(
+        id: Uuid,
+        status: Option<AnnouncementRecipientStatus>;
+        conn
+    ) -> Result<i64> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            r#"
+            SELECT count(*) as "count!"
+            FROM announcement_recipients
+            WHERE announcement_id = $1 AND ($2::announcement_recipient_status IS NULL OR status = $2)
+            "#,
+            id,
+            status as Option<AnnouncementRecipientStatus>,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(result.count)
+    }
+}
+
+impl_queries! {
+    for AnnouncementRecipient;
+
+    /// Get the recipients still waiting for their announcement, locking them until the end of the
+    /// transaction
+    ///
+    /// Recipients locked by other transactions are skipped so that many workers can deliver at
+    /// once.
+    #[instrument(name = "AnnouncementRecipient::pending", skip(conn))]
+    pub async fn pending(limit: i64; conn) -> Result<Vec<AnnouncementRecipient>> {
+        let mut conn = conn.acquire().await?;
+        let recipients = query_as!(
+            AnnouncementRecipient,
+            r#"
+            SELECT
+                announcement_id, participant_id, announcements.event, address,
+                status as "status: AnnouncementRecipientStatus", error, sent_at
+            FROM announcement_recipients
+            INNER JOIN announcements ON announcements.id = announcement_recipients.announcement_id
+            WHERE status = 'pending'
+            ORDER BY announcements.created_at, announcement_id, participant_id
+            LIMIT $1
+            FOR UPDATE OF announcement_recipients SKIP LOCKED
+            "#,
+            limit
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(recipients)
+    }
+
+    /// Record whether the announcement was delivered to the recipient
+    #[instrument(name = "AnnouncementRecipient::mark", skip(conn))]
+    pub async fn mark(
+        announcement_id: Uuid,
+        participant_id: i32,
+        error: Option<&'a str>;
+        conn
+    ) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            UPDATE announcement_recipients
+            SET
+                status = CASE WHEN $3::text IS NULL THEN 'sent' ELSE 'failed' END::announcement_recipient_status,
+                error = $3,
+                sent_at = CASE WHEN $3::text IS NULL THEN now() END
+            WHERE announcement_id = $1 AND participant_id = $2
+            "#,
+            announcement_id,
+            participant_id,
+            error,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+#[cfg(feature = "graphql")]
+use crate::ApplicationQuestion;
+use crate::{Result, SqlxError};
+#[cfg(feature = "graphql")]
+use async_graphql::{ComplexObject, Context, OneofObject, ResultExt, SimpleObject};
+use serde_json::Value;
+use sqlx::query;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// A participant's answer to one of the event's application questions
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct Answer {
+    /// The slug of the event the answer is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+    /// The ID of the participant that gave the answer
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub participant_id: i32,
+    /// The ID of the question being answered
+    pub question_id: Uuid,
+    /// The answer that was given
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub value: AnswerValue,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl Answer {
+    /// The question being answered
+    #[instrument(name = "Answer::question", skip_all)]
+    async fn question(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<ApplicationQuestion>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        ApplicationQuestion::find(&self.event, self.question_id, db)
+            .await
+            .extend()
+    }
+
+    /// The answer to a text or single choice question
+    async fn text(&self) -> Option<&str> {
+        match &self.value {
+            AnswerValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The answer to a number question
+    async fn number(&self) -> Option<i32> {
+        match self.value {
+            AnswerValue::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    /// The answer to a boolean question
+    async fn boolean(&self) -> Option<bool> {
+        match self.value {
+            AnswerValue::Boolean(boolean) => Some(boolean),
+            _ => None,
+        }
+    }
+
+    /// The options chosen for a multiple choice question
+    async fn choices(&self) -> Option<&[String]> {
+        match &self.value {
+            AnswerValue::Choices(choices) => Some(choices),
+            _ => None,
+        }
+    }
+}
+
+/// The value of an answer, exactly one of which must be given
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(OneofObject))]
+#[cfg_attr(feature = "graphql", graphql(name = "AnswerValueInput"))]
+pub enum AnswerValue {
+    /// The answer to a text or single choice question
+    Text(String),
+    /// The answer to a number question
+    Number(i32),
+    /// The answer to a boolean question
+    Boolean(bool),
+    /// The options chosen for a multiple choice question
+    Choices(Vec<String>),
+}
+
+impl AnswerValue {
+    /// Convert the answer to its stored representation
+    fn to_json(&self) -> Value {
+        match self {
+            Self::Text(text) => Value::from(text.as_str()),
+            Self::Number(number) => Value::from(*number),
+            Self::Boolean(boolean) => Value::from(*boolean),
+            Self::Choices(choices) => Value::from(choices.as_slice()),
+        }
+    }
+
+    /// Convert the answer from its stored representation
+    fn from_json(value: Value) -> Option<Self> {
+        match value {
+            Value::String(text) => Some(Self::Text(text)),
+            Value::Number(number) => number
+                .as_i64()
+                .and_then(|number| i32::try_from(number).ok())
+                .map(Self::Number),
+            Value::Bool(boolean) => Some(Self::Boolean(boolean)),
+            Value::Array(choices) => choices
+                .into_iter()
+                .map(|choice| match choice {
+                    Value::String(choice) => Some(choice),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(Self::Choices),
+            _ => None,
+        }
+    }
+}
+
+/// The error returned when a stored answer cannot be decoded
+fn invalid_value(question_id: Uuid) -> SqlxError {
+    SqlxError::Decode(format!("answer to question {question_id} has an invalid value").into())
+}
+
+impl_queries! {
+    for Answer;
+
+    /// Get the answers saved with a participant's draft application
+    #[instrument(name = "Answer::for_draft", skip(conn))]
+    pub async fn for_draft(event: &'a str, participant_id: i32; conn) -> Result<Vec<Answer>> {
+        let mut conn = conn.acquire().await?;
+        let answers = query!(
+            r#"
+            SELECT question_id, value
+            FROM draft_application_answers
+            WHERE participant_id = $1 AND event = $2
+            "#,
+            participant_id,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let value = AnswerValue::from_json(row.value)
+                .ok_or_else(|| invalid_value(row.question_id))?;
+            Ok(Answer {
+                event: event.to_owned(),
+                participant_id,
+                question_id: row.question_id,
+                value,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+        Ok(answers)
+    }
+
+    /// Get the answers submitted with a participant's application
+    #[instrument(name = "Answer::for_application", skip(conn))]
+    pub async fn for_application(event: &'a str, participant_id: i32; conn) -> Result<Vec<Answer>> {
+        let mut conn = conn.acquire().await?;
+        let answers = query!(
+            r#"
+            SELECT question_id, value
+            FROM application_answers
+            WHERE participant_id = $1 AND event = $2
+            "#,
+            participant_id,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let value = AnswerValue::from_json(row.value)
+                .ok_or_else(|| invalid_value(row.question_id))?;
+            Ok(Answer {
+                event: event.to_owned(),
+                participant_id,
+                question_id: row.question_id,
+                value,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+        Ok(answers)
+    }
+
+    /// Save an answer to a participant's draft application
+    #[instrument(name = "Answer::save_draft", skip(value, conn))]
+    pub async fn save_draft(
+        event: &'a str,
+        participant_id: i32,
+        question_id: Uuid,
+        value: &'a AnswerValue;
+        conn
+    ) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            INSERT INTO draft_application_answers (event, participant_id, question_id, value)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (participant_id, event, question_id)
+            DO UPDATE SET value = excluded.value
+            "#,
+            event,
+            participant_id,
+            question_id,
+            value.to_json(),
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove an answer from a participant's draft application
+    #[instrument(name = "Answer::delete_draft", skip(conn))]
+    pub async fn delete_draft(event: &'a str, participant_id: i32, question_id: Uuid; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            DELETE FROM draft_application_answers
+            WHERE participant_id = $1 AND event = $2 AND question_id = $3
+            "#,
+            participant_id,
+            event,
+            question_id
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Copy the answers from a participant's draft application to their submitted application
+    #[instrument(name = "Answer::copy_from_draft", skip(conn))]
+    pub async fn copy_from_draft(event: &'a str, participant_id: i32; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            INSERT INTO application_answers (event, participant_id, question_id, value)
+            SELECT event, participant_id, question_id, value
+            FROM draft_application_answers
+            WHERE participant_id = $1 AND event = $2
+            "#,
+            participant_id,
+            event
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,22 +1,30 @@
-use crate::Result;
 #[cfg(feature = "graphql")]
 use crate::{
     stubs::{Event, Participant},
-    School,
+    Answer, ApplicationAuditEntry, CheckIn, CheckpointScan, Resume, Review, School,
+};
+use crate::{
+    validation::{Fields, ValidationError},
+    Result,
 };
 #[cfg(feature = "graphql")]
-use async_graphql::{ComplexObject, Context, Enum, ResultExt, SimpleObject};
+use async_graphql::{
+    connection::CursorType, ComplexObject, Context, Enum, InputObject, ResultExt, SimpleObject,
+};
 use chrono::{DateTime, NaiveDate, Utc};
 #[cfg(feature = "graphql")]
 use context::{
-    checks::{guard_where, has_at_least_role},
+    checks::{self, guard_where, has_at_least_role},
     UserRole,
 };
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 #[cfg(feature = "graphql")]
 use serde::Serialize;
-use sqlx::{query, query_as, Acquire, QueryBuilder};
+use sqlx::{
+    postgres::{PgHasArrayType, PgTypeInfo},
+    query, query_as, Acquire, Postgres, QueryBuilder,
+};
 use std::future::Future;
 use tracing::instrument;
 use uuid::Uuid;
@@ -111,9 +119,29 @@ pub enum ApplicationStatus {
     Waitlisted,
     Rejected,
     Accepted,
+    Confirmed,
+    Declined,
+    Withdrawn,
+}
+
+impl PgHasArrayType for ApplicationStatus {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_application_status")
+    }
 }
 
 impl ApplicationStatus {
+    /// The statuses of applications whose participants can check in to the event
+    ///
+    /// Accepted participants who have not confirmed their attendance yet are only included when
+    /// the event does not require confirming first.
+    pub fn check_in_statuses(confirmed_only: bool) -> &'static [ApplicationStatus] {
+        match confirmed_only {
+            true => &[Self::Confirmed],
+            false => &[Self::Accepted, Self::Confirmed],
+        }
+    }
+
     /// Convert the application to a string
     pub fn to_str(&self) -> &'static str {
         match self {
@@ -121,12 +149,15 @@ impl ApplicationStatus {
             Self::Waitlisted => "waitlisted",
             Self::Rejected => "rejected",
             Self::Accepted => "accepted",
+            Self::Confirmed => "confirmed",
+            Self::Declined => "declined",
+            Self::Withdrawn => "withdrawn",
         }
     }
 }
 
 /// An application to an event
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, sqlx::FromRow)]
 #[cfg_attr(feature = "graphql", derive(SimpleObject))]
 #[cfg_attr(feature = "graphql", graphql(complex))]
 #[cfg_attr(feature = "graphql", derive(Serialize))]
@@ -254,6 +285,109 @@ impl Application {
             None => Ok(None),
         }
     }
+
+    /// The resume uploaded with the application, if any
+    #[instrument(name = "Application::resume", skip_all)]
+    async fn resume(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Resume>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Resume::find(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
+
+    /// The answers given to the event's application questions
+    #[instrument(name = "Application::answers", skip_all)]
+    async fn answers(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Answer>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Answer::for_application(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
+
+    /// The number of reviews the application has received
+    #[graphql(guard = "guard_where(has_at_least_role, UserRole::Organizer)")]
+    #[instrument(name = "Application::review_count", skip_all)]
+    async fn review_count(&self, ctx: &Context<'_>) -> async_graphql::Result<i64> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Review::count(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
+
+    /// The average total score given across all reviews, if any
+    #[graphql(guard = "guard_where(has_at_least_role, UserRole::Organizer)")]
+    #[instrument(name = "Application::average_score", skip_all)]
+    async fn average_score(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<f64>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Review::average_score(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
+
+    /// When the participant must confirm their attendance by, if the event limits it
+    #[instrument(name = "Application::rsvp_deadline", skip_all)]
+    async fn rsvp_deadline(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<DateTime<Utc>>> {
+        if self.status != ApplicationStatus::Accepted {
+            return Ok(None);
+        }
+
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Application::find_rsvp_deadline(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
+
+    /// The participant's check in, if they checked in to the event
+    #[instrument(name = "Application::check_in", skip_all)]
+    async fn check_in(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<CheckIn>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        CheckIn::find(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
+
+    /// The changes the participant made to the application after submitting it, oldest first
+    #[graphql(guard = "guard_where(has_at_least_role, UserRole::Organizer)")]
+    #[instrument(name = "Application::audit_log", skip_all)]
+    async fn audit_log(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<ApplicationAuditEntry>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        ApplicationAuditEntry::for_application(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
+
+    /// Every time the participant was scanned at one of the event's checkpoints, newest first
+    #[graphql(guard = "guard_where(has_at_least_role, UserRole::Organizer)")]
+    #[instrument(name = "Application::checkpoint_scans", skip_all)]
+    async fn checkpoint_scans(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<CheckpointScan>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        CheckpointScan::for_participant(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
+
+    /// The requesting organizer's own review of the application
+    ///
+    /// Reviews from other organizers are not exposed to keep review rounds blind.
+    #[graphql(guard = "guard_where(has_at_least_role, UserRole::Organizer)")]
+    #[instrument(name = "Application::review", skip_all)]
+    async fn review(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Review>> {
+        let user = checks::is_authenticated(ctx)?;
+
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Review::find(&self.event, self.participant_id, user.id, db)
+            .await
+            .extend()
+    }
 }
 
 impl_queries! {
@@ -274,19 +408,39 @@ impl_queries! {
         Ok(result.exists.unwrap_or_default())
     }
 
-    /// Check if an accepted application exists
+    /// Check if an accepted application exists, including those that confirmed their attendance
     #[instrument(name = "Application::accepted_exists", skip(conn))]
     pub async fn accepted_exists(event: &'a str, participant_id: i32; conn) -> Result<bool> {
+        let statuses = ApplicationStatus::check_in_statuses(false);
+        Application::status_exists(event, participant_id, statuses, conn).await
+    }
+
+    /// Check if a confirmed application exists
+    #[instrument(name = "Application::confirmed_exists", skip(conn))]
+    pub async fn confirmed_exists(event: &'a str, participant_id: i32; conn) -> Result<bool> {
+        let statuses = ApplicationStatus::check_in_statuses(true);
+        Application::status_exists(event, participant_id, statuses, conn).await
+    }
+
+    /// Check if an application with any of the statuses exists
+    #[instrument(name = "Application::status_exists", skip(conn))]
+    async fn status_exists(
+        event: &'a str,
+        participant_id: i32,
+        statuses: &'a [ApplicationStatus];
+        conn
+    ) -> Result<bool> {
         let mut conn = conn.acquire().await?;
         let result = query!(
             r#"
             SELECT exists(
                 SELECT 1 FROM applications
-                WHERE status = 'accepted' AND participant_id = $1 AND event = $2
+                WHERE status = ANY($3) AND participant_id = $1 AND event = $2
             )
             "#,
             participant_id,
-            event
+            event,
+            statuses as &[ApplicationStatus],
         )
         .fetch_one(&mut *conn)
         .await?;
@@ -294,6 +448,63 @@ impl_queries! {
         Ok(result.exists.unwrap_or_default())
     }
 
+    /// Get the deadline for an accepted participant to confirm their attendance by
+    ///
+    /// There is no deadline if the event does not limit how long participants have to respond.
+    #[instrument(name = "Application::find_rsvp_deadline", skip(conn))]
+    pub async fn find_rsvp_deadline(
+        event: &'a str,
+        participant_id: i32;
+        conn
+    ) -> Result<Option<DateTime<Utc>>> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            r#"
+            SELECT applications.accepted_at + make_interval(days => event_settings.rsvp_window_days) as deadline
+            FROM applications
+            INNER JOIN event_settings ON event_settings.event = applications.event
+            WHERE applications.participant_id = $1 AND applications.event = $2
+            "#,
+            participant_id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(result.and_then(|row| row.deadline))
+    }
+
+    /// Decline all the accepted applications that missed their RSVP deadline, across all events
+    #[instrument(name = "Application::decline_expired_rsvps", skip_all)]
+    pub async fn decline_expired_rsvps(; conn) -> Result<Vec<Application>> {
+        let mut conn = conn.acquire().await?;
+        let applications = query_as!(
+            Application,
+            r#"
+            UPDATE applications
+            SET status = 'declined'
+            FROM event_settings
+            WHERE event_settings.event = applications.event
+                AND applications.status = 'accepted'
+                AND applications.accepted_at + make_interval(days => event_settings.rsvp_window_days) < now()
+            RETURNING
+                applications.event, participant_id,
+                gender as "gender: Gender", race_ethnicity as "race_ethnicity: RaceEthnicity",
+                date_of_birth, referrer as "referrer: Referrer",
+                school_id, education as "education: Education", graduation_year, major,
+                hackathons_attended, vcs_url, portfolio_url, devpost_url,
+                address_line1, address_line2, address_line3, locality, administrative_area,
+                postal_code, country, share_information,
+                status as "status: ApplicationStatus", flagged, notes,
+                applications.created_at, applications.updated_at
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(applications)
+    }
+
     /// Get all the submitted applications for an event
     #[instrument(name = "Application::all", skip(conn))]
     pub async fn all(event: &'a str; conn) -> Result<Vec<Application>> {
@@ -322,6 +533,68 @@ impl_queries! {
         Ok(applications)
     }
 
+    /// Count the submitted applications for an event that match the filter
+    #[instrument(name = "Application::count", skip(conn))]
+    pub async fn count(event: &'a str, filter: &'a ApplicationFilter; conn) -> Result<i64> {
+        let mut builder = QueryBuilder::new("SELECT count(*) FROM applications WHERE event = ");
+        builder.push_bind(event);
+        filter.push_conditions(&mut builder);
+
+        let mut conn = conn.acquire().await?;
+        let (count,) = builder
+            .build_query_as::<(i64,)>()
+            .fetch_one(&mut *conn)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Get a page of submitted applications for an event that match the filter
+    ///
+    /// Only applications sorting strictly after the cursor are returned. If no limit is provided,
+    /// all the matching applications are returned.
+    #[instrument(name = "Application::list", skip(conn))]
+    pub async fn list(
+        event: &'a str,
+        filter: &'a ApplicationFilter,
+        order: ApplicationOrder,
+        after: Option<ApplicationCursor>,
+        limit: Option<i64>;
+        conn
+    ) -> Result<Vec<Application>> {
+        let mut builder = QueryBuilder::new("SELECT * FROM applications WHERE event = ");
+        builder.push_bind(event);
+        filter.push_conditions(&mut builder);
+
+        let column = order.column();
+        if let Some(after) = after {
+            builder.push(format_args!(" AND ({column}, participant_id) "));
+            builder.push(if order.is_descending() { "< (" } else { "> (" });
+            builder.push_bind(after.at);
+            builder.push(", ");
+            builder.push_bind(after.participant_id);
+            builder.push(")");
+        }
+
+        let direction = if order.is_descending() { "DESC" } else { "ASC" };
+        builder.push(format_args!(
+            " ORDER BY {column} {direction}, participant_id {direction}"
+        ));
+
+        if let Some(limit) = limit {
+            builder.push(" LIMIT ");
+            builder.push_bind(limit);
+        }
+
+        let mut conn = conn.acquire().await?;
+        let applications = builder
+            .build_query_as::<Application>()
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Ok(applications)
+    }
+
     /// Get an application by its event and participant id
     #[instrument(name = "Application::find", skip(conn))]
     pub async fn find(event: &'a str, participant_id: i32; conn) -> Result<Option<Application>> {
@@ -351,6 +624,101 @@ impl_queries! {
         Ok(application)
     }
 
+    /// Get many applications by their event and participant ids
+    #[instrument(name = "Application::find_many", skip(conn))]
+    pub async fn find_many(event: &'a str, participant_ids: &'a [i32]; conn) -> Result<Vec<Application>> {
+        let mut conn = conn.acquire().await?;
+        let applications = query_as!(
+            Application,
+            r#"
+            SELECT
+                event, participant_id,
+                gender as "gender: Gender", race_ethnicity as "race_ethnicity: RaceEthnicity",
+                date_of_birth, referrer as "referrer: Referrer",
+                school_id, education as "education: Education", graduation_year, major,
+                hackathons_attended, vcs_url, portfolio_url, devpost_url,
+                address_line1, address_line2, address_line3, locality, administrative_area,
+                postal_code, country, share_information,
+                status as "status: ApplicationStatus", flagged, notes,
+                created_at, updated_at
+            FROM applications
+            WHERE participant_id = ANY($1) AND event = $2
+            "#,
+            participant_ids,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(applications)
+    }
+
+    /// Get the next applications on an event's waitlist, in the order they were waitlisted
+    #[instrument(name = "Application::waitlist", skip(conn))]
+    pub async fn waitlist(event: &'a str, limit: i64; conn) -> Result<Vec<Application>> {
+        let mut conn = conn.acquire().await?;
+        let applications = query_as!(
+            Application,
+            r#"
+            SELECT
+                event, participant_id,
+                gender as "gender: Gender", race_ethnicity as "race_ethnicity: RaceEthnicity",
+                date_of_birth, referrer as "referrer: Referrer",
+                school_id, education as "education: Education", graduation_year, major,
+                hackathons_attended, vcs_url, portfolio_url, devpost_url,
+                address_line1, address_line2, address_line3, locality, administrative_area,
+                postal_code, country, share_information,
+                status as "status: ApplicationStatus", flagged, notes,
+                created_at, updated_at
+            FROM applications
+            WHERE status = 'waitlisted' AND event = $1
+            ORDER BY waitlisted_at, created_at, participant_id
+            LIMIT $2
+            "#,
+            event,
+            limit
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(applications)
+    }
+
+    /// Set the status of many applications at once, returning the updated applications
+    #[instrument(name = "Application::set_status_many", skip(conn))]
+    pub async fn set_status_many(
+        event: &'a str,
+        participant_ids: &'a [i32],
+        status: ApplicationStatus;
+        conn
+    ) -> Result<Vec<Application>> {
+        let mut conn = conn.acquire().await?;
+        let applications = query_as!(
+            Application,
+            r#"
+            UPDATE applications SET status = $3
+            WHERE participant_id = ANY($1) AND event = $2
+            RETURNING
+                event, participant_id,
+                gender as "gender: Gender", race_ethnicity as "race_ethnicity: RaceEthnicity",
+                date_of_birth, referrer as "referrer: Referrer",
+                school_id, education as "education: Education", graduation_year, major,
+                hackathons_attended, vcs_url, portfolio_url, devpost_url,
+                address_line1, address_line2, address_line3, locality, administrative_area,
+                postal_code, country, share_information,
+                status as "status: ApplicationStatus", flagged, notes,
+                created_at, updated_at
+            "#,
+            participant_ids,
+            event,
+            status as _,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(applications)
+    }
+
     /// Create a new application from a draft
     #[instrument(name = "Application::from_draft", skip(conn))]
     pub async fn from_draft(event: &'a str, participant_id: i32; conn) -> Result<Self> {
@@ -392,6 +760,67 @@ impl_queries! {
         Ok(application)
     }
 
+    /// Get the events a participant has an application, draft application, or check in for
+    #[instrument(name = "Application::events_for_participant", skip(conn))]
+    pub async fn events_for_participant(participant_id: i32; conn) -> Result<Vec<String>> {
+        let mut conn = conn.acquire().await?;
+        let events = query!(
+            r#"
+            SELECT event as "event!" FROM applications WHERE participant_id = $1
+            UNION SELECT event FROM draft_applications WHERE participant_id = $1
+            UNION SELECT event FROM check_ins WHERE participant_id = $1
+            "#,
+            participant_id
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(events.into_iter().map(|row| row.event).collect())
+    }
+
+    /// Get the participants with an application, draft application, or check in for an event
+    #[instrument(name = "Application::participants_for_event", skip(conn))]
+    pub async fn participants_for_event(event: &'a str; conn) -> Result<Vec<i32>> {
+        let mut conn = conn.acquire().await?;
+        let participants = query!(
+            r#"
+            SELECT participant_id as "participant_id!" FROM applications WHERE event = $1
+            UNION SELECT participant_id FROM draft_applications WHERE event = $1
+            UNION SELECT participant_id FROM check_ins WHERE event = $1
+            "#,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(participants.into_iter().map(|row| row.participant_id).collect())
+    }
+
+    /// Get the IDs of the participants allowed to check in to an event
+    ///
+    /// Participants must be accepted, or have confirmed their attendance when required.
+    #[instrument(name = "Application::eligible_for_check_in", skip(conn))]
+    pub async fn eligible_for_check_in(
+        event: &'a str,
+        confirmed_only: bool;
+        conn
+    ) -> Result<Vec<i32>> {
+        let mut conn = conn.acquire().await?;
+        let participants = query!(
+            r#"
+            SELECT participant_id FROM applications
+            WHERE event = $1 AND status = ANY($2)
+            ORDER BY participant_id
+            "#,
+            event,
+            ApplicationStatus::check_in_statuses(confirmed_only) as &[ApplicationStatus],
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(participants.into_iter().map(|row| row.participant_id).collect())
+    }
+
     /// Delete an application
     #[instrument(name = "Application::delete", skip(conn))]
     pub async fn delete(event: &'a str, participant_id: i32; conn) -> Result<()> {
@@ -409,10 +838,270 @@ impl_queries! {
 }
 
 impl Application {
+    /// Check the application's fields, returning an error for every invalid one
+    pub fn validate(&self) -> Vec<ValidationError> {
+        Fields {
+            date_of_birth: Some(self.date_of_birth),
+            graduation_year: Some(self.graduation_year),
+            major: self.major.as_deref(),
+            hackathons_attended: Some(self.hackathons_attended),
+            vcs_url: self.vcs_url.as_deref(),
+            portfolio_url: self.portfolio_url.as_deref(),
+            devpost_url: self.devpost_url.as_deref(),
+            address_line1: Some(&self.address_line1),
+            address_line2: self.address_line2.as_deref(),
+            address_line3: self.address_line3.as_deref(),
+            locality: self.locality.as_deref(),
+            administrative_area: self.administrative_area.as_deref(),
+            postal_code: Some(&self.postal_code),
+            country: Some(&self.country),
+        }
+        .validate()
+    }
+
     /// Update the application's fields
     pub fn update(&mut self) -> ApplicationUpdater<'_> {
         ApplicationUpdater::new(self)
     }
+
+    /// Save the participant-provided details of the application
+    ///
+    /// The status and organizer-only fields are left untouched, use [`Application::update`] for
+    /// those instead.
+    #[instrument(
+        name = "Application::save_details",
+        skip_all,
+        fields(event = self.event, participant_id = self.participant_id)
+    )]
+    #[allow(clippy::manual_async_fn)]
+    pub fn save_details<'a, 'c, A>(&'a self, db: A) -> impl Future<Output = Result<()>> + Send + 'a
+    where
+        A: 'a + Acquire<'c, Database = sqlx::Postgres> + Send,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            query!(
+                r#"
+                UPDATE applications
+                SET
+                    gender = $3,
+                    race_ethnicity = $4,
+                    date_of_birth = $5,
+                    referrer = $6,
+                    school_id = $7,
+                    education = $8,
+                    graduation_year = $9,
+                    major = $10,
+                    hackathons_attended = $11,
+                    vcs_url = $12,
+                    portfolio_url = $13,
+                    devpost_url = $14,
+                    address_line1 = $15,
+                    address_line2 = $16,
+                    address_line3 = $17,
+                    locality = $18,
+                    administrative_area = $19,
+                    postal_code = $20,
+                    country = $21,
+                    share_information = $22
+                WHERE participant_id = $1 AND event = $2
+                "#,
+                self.participant_id,
+                self.event,
+                self.gender as _,
+                self.race_ethnicity as _,
+                self.date_of_birth,
+                self.referrer as _,
+                self.school_id,
+                self.education as _,
+                self.graduation_year,
+                self.major,
+                self.hackathons_attended,
+                self.vcs_url,
+                self.portfolio_url,
+                self.devpost_url,
+                self.address_line1,
+                self.address_line2,
+                self.address_line3,
+                self.locality,
+                self.administrative_area,
+                self.postal_code,
+                self.country,
+                self.share_information,
+            )
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        }
+    }
+}
+
+/// Conditions that applications must match when being listed
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(InputObject))]
+pub struct ApplicationFilter {
+    /// Only include applications with the status
+    pub status: Option<ApplicationStatus>,
+    /// Only include applications that are (or are not) flagged
+    pub flagged: Option<bool>,
+    /// Only include applications that were (or were not) accepted, including those that confirmed
+    /// their attendance
+    pub accepted: Option<bool>,
+    /// Only treat applications that confirmed their attendance as accepted, as when the event
+    /// requires confirming before checking in
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub confirmed_only: bool,
+    /// Only include participants with the level of education
+    pub education: Option<Education>,
+    /// Only include participants attending the school
+    pub school_id: Option<Uuid>,
+    /// Only include participants graduating in the year
+    pub graduation_year: Option<i32>,
+    /// Only include applications submitted at or after the time
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only include applications submitted before the time
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only include participants who have (or have not) checked in
+    pub checked_in: Option<bool>,
+}
+
+impl ApplicationFilter {
+    /// Add the filter's conditions to a query on `applications` with an existing `WHERE` clause
+    pub(crate) fn push_conditions<'args>(&'args self, builder: &mut QueryBuilder<'args, Postgres>) {
+        if let Some(status) = self.status {
+            builder.push(" AND status = ");
+            builder.push_bind(status);
+        }
+
+        if let Some(flagged) = self.flagged {
+            builder.push(" AND flagged = ");
+            builder.push_bind(flagged);
+        }
+
+        if let Some(accepted) = self.accepted {
+            builder.push(if accepted { " AND " } else { " AND NOT " });
+            builder.push("status = ANY(");
+            builder.push_bind(ApplicationStatus::check_in_statuses(self.confirmed_only));
+            builder.push(")");
+        }
+
+        if let Some(education) = self.education {
+            builder.push(" AND education = ");
+            builder.push_bind(education);
+        }
+
+        if let Some(school_id) = self.school_id {
+            builder.push(" AND school_id = ");
+            builder.push_bind(school_id);
+        }
+
+        if let Some(graduation_year) = self.graduation_year {
+            builder.push(" AND graduation_year = ");
+            builder.push_bind(graduation_year);
+        }
+
+        if let Some(created_after) = self.created_after {
+            builder.push(" AND created_at >= ");
+            builder.push_bind(created_after);
+        }
+
+        if let Some(created_before) = self.created_before {
+            builder.push(" AND created_at < ");
+            builder.push_bind(created_before);
+        }
+
+        if let Some(checked_in) = self.checked_in {
+            builder.push(if checked_in { " AND " } else { " AND NOT " });
+            builder.push(
+                "exists(\
+                    SELECT 1 FROM check_ins \
+                    WHERE check_ins.participant_id = applications.participant_id \
+                    AND check_ins.event = applications.event\
+                )",
+            );
+        }
+    }
+}
+
+/// How to sort applications when they are listed
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(Enum))]
+pub enum ApplicationOrder {
+    /// Oldest submissions first
+    #[default]
+    CreatedAtAsc,
+    /// Newest submissions first
+    CreatedAtDesc,
+    /// Least recently modified first
+    UpdatedAtAsc,
+    /// Most recently modified first
+    UpdatedAtDesc,
+}
+
+impl ApplicationOrder {
+    /// The column the applications are sorted by
+    fn column(&self) -> &'static str {
+        match self {
+            Self::CreatedAtAsc | Self::CreatedAtDesc => "created_at",
+            Self::UpdatedAtAsc | Self::UpdatedAtDesc => "updated_at",
+        }
+    }
+
+    /// Whether the applications are sorted from largest to smallest
+    fn is_descending(&self) -> bool {
+        matches!(self, Self::CreatedAtDesc | Self::UpdatedAtDesc)
+    }
+}
+
+/// The position of an application within a sorted listing
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ApplicationCursor {
+    /// The value of the column being sorted by
+    pub at: DateTime<Utc>,
+    /// The ID of the participant, used to break ties
+    pub participant_id: i32,
+}
+
+impl ApplicationCursor {
+    /// Get the position of an application for the given sort order
+    pub fn new(application: &Application, order: ApplicationOrder) -> Self {
+        let at = match order {
+            ApplicationOrder::CreatedAtAsc | ApplicationOrder::CreatedAtDesc => {
+                application.created_at
+            }
+            ApplicationOrder::UpdatedAtAsc | ApplicationOrder::UpdatedAtDesc => {
+                application.updated_at
+            }
+        };
+
+        Self {
+            at,
+            participant_id: application.participant_id,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl CursorType for ApplicationCursor {
+    type Error = &'static str;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        let (at, participant_id) = s.split_once(':').ok_or("malformed cursor")?;
+
+        let at = at
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or("invalid cursor timestamp")?;
+        let participant_id = participant_id.parse().map_err(|_| "invalid cursor id")?;
+
+        Ok(Self { at, participant_id })
+    }
+
+    fn encode_cursor(&self) -> String {
+        format!("{}:{}", self.at.timestamp_micros(), self.participant_id)
+    }
 }
 
 /// A person's shipping address
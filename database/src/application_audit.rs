@@ -0,0 +1,119 @@
+#[cfg(feature = "graphql")]
+use crate::stubs::User;
+use crate::Result;
+#[cfg(feature = "graphql")]
+use async_graphql::{ComplexObject, Enum, Json, SimpleObject};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{query, query_as};
+use tracing::instrument;
+use uuid::Uuid;
+
+/// The changes that can be made to a submitted application by its participant
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[cfg_attr(feature = "graphql", derive(Enum))]
+#[sqlx(rename_all = "lowercase", type_name = "application_audit_action")]
+pub enum ApplicationAuditAction {
+    Updated,
+    Withdrawn,
+}
+
+/// A record of a change made to a submitted application
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct ApplicationAuditEntry {
+    /// The entry's unique ID
+    pub id: Uuid,
+    /// The slug of the event the application is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+    /// The ID of the participant that submitted the application
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub participant_id: i32,
+    /// The ID of the user that made the change
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub actor_id: i32,
+
+    /// What was done to the application
+    pub action: ApplicationAuditAction,
+    /// The fields that were changed, mapped to their previous and new values
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub changes: Value,
+
+    /// When the change was made
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl ApplicationAuditEntry {
+    /// The user who made the change
+    async fn actor(&self) -> User {
+        User { id: self.actor_id }
+    }
+
+    /// The fields that were changed, mapped to their previous and new values
+    async fn changes(&self) -> Json<Value> {
+        Json(self.changes.clone())
+    }
+}
+
+impl_queries! {
+    for ApplicationAuditEntry;
+
+    /// Record a change made to a submitted application
+    #[instrument(name = "ApplicationAuditEntry::record", skip(changes, conn))]
+    pub async fn record(
+        event: &'a str,
+        participant_id: i32,
+        actor_id: i32,
+        action: ApplicationAuditAction,
+        changes: &'a Value;
+        conn
+    ) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            INSERT INTO application_audit_log (event, participant_id, actor_id, action, changes)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            event,
+            participant_id,
+            actor_id,
+            action as _,
+            changes,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get the changes made to a submitted application, oldest first
+    #[instrument(name = "ApplicationAuditEntry::for_application", skip(conn))]
+    pub async fn for_application(
+        event: &'a str,
+        participant_id: i32;
+        conn
+    ) -> Result<Vec<ApplicationAuditEntry>> {
+        let mut conn = conn.acquire().await?;
+        let entries = query_as!(
+            ApplicationAuditEntry,
+            r#"
+            SELECT
+                id, event, participant_id, actor_id,
+                action as "action: ApplicationAuditAction", changes, created_at
+            FROM application_audit_log
+            WHERE participant_id = $1 AND event = $2
+            ORDER BY created_at
+            "#,
+            participant_id,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,468 @@
+use crate::{validation::ValidationError, Answer, AnswerValue, Result};
+#[cfg(feature = "graphql")]
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as, Acquire, QueryBuilder};
+use std::future::Future;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// The longest a short text answer can be when no maximum is set
+const DEFAULT_MAX_SHORT_TEXT_LENGTH: i32 = 256;
+/// The longest a long text answer can be when no maximum is set
+const DEFAULT_MAX_LONG_TEXT_LENGTH: i32 = 4096;
+
+/// The type of answer a question accepts
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[cfg_attr(feature = "graphql", derive(Enum))]
+#[sqlx(rename_all = "kebab-case", type_name = "application_question_kind")]
+pub enum ApplicationQuestionKind {
+    /// A single line of text
+    ShortText,
+    /// Multiple paragraphs of text
+    LongText,
+    /// Exactly one of the question's options
+    SingleChoice,
+    /// Any number of the question's options
+    MultipleChoice,
+    /// A whole number
+    Number,
+    /// A yes or no answer
+    Boolean,
+}
+
+impl ApplicationQuestionKind {
+    /// What an answer must be for this kind of question
+    fn expected(&self) -> &'static str {
+        match self {
+            Self::ShortText | Self::LongText => "must be text",
+            Self::SingleChoice => "must be one of the options",
+            Self::MultipleChoice => "must be a list of options",
+            Self::Number => "must be a number",
+            Self::Boolean => "must be true or false",
+        }
+    }
+
+    /// The units the bounds of this kind of question are measured in
+    fn units(&self) -> &'static str {
+        match self {
+            Self::ShortText | Self::LongText => " characters",
+            Self::MultipleChoice => " options",
+            Self::SingleChoice | Self::Number | Self::Boolean => "",
+        }
+    }
+}
+
+/// An organizer-defined question that participants answer as part of their application
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+pub struct ApplicationQuestion {
+    /// The question's unique ID
+    pub id: Uuid,
+    /// The slug of the event the question is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+
+    /// The question being asked
+    pub label: String,
+    /// Additional details about the question
+    pub description: String,
+    /// The type of answer the question accepts
+    pub kind: ApplicationQuestionKind,
+    /// Whether the question must be answered before the application can be submitted
+    pub required: bool,
+    /// Where the question appears in the application, in ascending order
+    pub position: i32,
+
+    /// The options that can be chosen for choice questions
+    pub options: Vec<String>,
+    /// The lower bound of an answer, inclusive
+    ///
+    /// This is the length of text answers, the number of options chosen for multiple choice
+    /// answers, and the value of number answers.
+    pub minimum: Option<i32>,
+    /// The upper bound of an answer, inclusive
+    ///
+    /// This is the length of text answers, the number of options chosen for multiple choice
+    /// answers, and the value of number answers.
+    pub maximum: Option<i32>,
+
+    /// When the question was created
+    pub created_at: DateTime<Utc>,
+    /// When the question was last modified
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ApplicationQuestion {
+    /// Check that an answer is acceptable for the question, returning why it is not
+    pub fn check(&self, value: &AnswerValue) -> Result<(), String> {
+        let size = match (self.kind, value) {
+            (
+                ApplicationQuestionKind::ShortText | ApplicationQuestionKind::LongText,
+                AnswerValue::Text(text),
+            ) => i32::try_from(text.chars().count()).unwrap_or(i32::MAX),
+            (ApplicationQuestionKind::SingleChoice, AnswerValue::Text(choice)) => {
+                return match self.options.contains(choice) {
+                    true => Ok(()),
+                    false => Err(self.kind.expected().to_owned()),
+                };
+            }
+            (ApplicationQuestionKind::MultipleChoice, AnswerValue::Choices(choices)) => {
+                if !choices.iter().all(|choice| self.options.contains(choice)) {
+                    return Err(String::from("must only contain the options"));
+                }
+
+                i32::try_from(choices.len()).unwrap_or(i32::MAX)
+            }
+            (ApplicationQuestionKind::Number, AnswerValue::Number(number)) => *number,
+            (ApplicationQuestionKind::Boolean, AnswerValue::Boolean(_)) => return Ok(()),
+            _ => return Err(self.kind.expected().to_owned()),
+        };
+
+        let units = self.kind.units();
+        if let Some(minimum) = self.minimum {
+            if size < minimum {
+                return Err(format!("must be at least {minimum}{units}"));
+            }
+        }
+
+        let maximum = match self.kind {
+            ApplicationQuestionKind::ShortText => {
+                Some(self.maximum.unwrap_or(DEFAULT_MAX_SHORT_TEXT_LENGTH))
+            }
+            ApplicationQuestionKind::LongText => {
+                Some(self.maximum.unwrap_or(DEFAULT_MAX_LONG_TEXT_LENGTH))
+            }
+            _ => self.maximum,
+        };
+        if let Some(maximum) = maximum {
+            if size > maximum {
+                return Err(format!("must be at most {maximum}{units}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check each of the answers against the event's questions, returning an error for every
+    /// invalid one
+    ///
+    /// When `complete` is set, every required question must also have been answered.
+    pub fn validate_answers(
+        questions: &[ApplicationQuestion],
+        answers: &[Answer],
+        complete: bool,
+    ) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        for question in questions {
+            match answers
+                .iter()
+                .find(|answer| answer.question_id == question.id)
+            {
+                Some(answer) => {
+                    if let Err(reason) = question.check(&answer.value) {
+                        errors.push(ValidationError::item(
+                            &["input", "answers"],
+                            question.id,
+                            format!("the answer to \"{}\" {reason}", question.label),
+                        ));
+                    }
+                }
+                None if complete && question.required => errors.push(ValidationError::item(
+                    &["input", "answers"],
+                    question.id,
+                    format!("\"{}\" must be answered", question.label),
+                )),
+                None => {}
+            }
+        }
+
+        errors
+    }
+}
+
+impl_queries! {
+    for ApplicationQuestion;
+
+    /// Get all the application questions for an event, in the order they appear
+    #[instrument(name = "ApplicationQuestion::all", skip(conn))]
+    pub async fn all(event: &'a str; conn) -> Result<Vec<ApplicationQuestion>> {
+        let mut conn = conn.acquire().await?;
+        let questions = query_as!(
+            ApplicationQuestion,
+            r#"
+            SELECT
+                id, event, label, description, kind as "kind: ApplicationQuestionKind", required,
+                position, options, minimum, maximum, created_at, updated_at
+            FROM application_questions
+            WHERE event = $1
+            ORDER BY position, created_at
+            "#,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(questions)
+    }
+
+    /// Find an application question by its ID
+    #[instrument(name = "ApplicationQuestion::find", skip(conn))]
+    pub async fn find(event: &'a str, id: Uuid; conn) -> Result<Option<ApplicationQuestion>> {
+        let mut conn = conn.acquire().await?;
+        let question = query_as!(
+            ApplicationQuestion,
+            r#"
+            SELECT
+                id, event, label, description, kind as "kind: ApplicationQuestionKind", required,
+                position, options, minimum, maximum, created_at, updated_at
+            FROM application_questions
+            WHERE id = $1 AND event = $2
+            "#,
+            id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(question)
+    }
+
+    /// Create a new application question
+    #[instrument(name = "ApplicationQuestion::create", skip(conn))]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        event: &'a str,
+        label: &'a str,
+        description: &'a str,
+        kind: ApplicationQuestionKind,
+        required: bool,
+        position: i32,
+        options: &'a [String],
+        minimum: Option<i32>,
+        maximum: Option<i32>;
+        conn
+    ) -> Result<ApplicationQuestion> {
+        let mut conn = conn.acquire().await?;
+        let question = query_as!(
+            ApplicationQuestion,
+            r#"
+            INSERT INTO application_questions (
+                event, label, description, kind, required, position, options, minimum, maximum
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING
+                id, event, label, description, kind as "kind: ApplicationQuestionKind", required,
+                position, options, minimum, maximum, created_at, updated_at
+            "#,
+            event,
+            label,
+            description,
+            kind as _,
+            required,
+            position,
+            options,
+            minimum,
+            maximum,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(question)
+    }
+
+    /// Delete an application question, along with any answers given for it
+    #[instrument(name = "ApplicationQuestion::delete", skip(conn))]
+    pub async fn delete(event: &'a str, id: Uuid; conn) -> Result<bool> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            "DELETE FROM application_questions WHERE id = $1 AND event = $2",
+            id,
+            event
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+impl ApplicationQuestion {
+    /// Update the question's fields
+    pub fn update(&mut self) -> ApplicationQuestionUpdater<'_> {
+        ApplicationQuestionUpdater::new(self)
+    }
+}
+
+/// Handles updating an application question
+pub struct ApplicationQuestionUpdater<'a> {
+    question: &'a mut ApplicationQuestion,
+    label: Option<String>,
+    description: Option<String>,
+    required: Option<bool>,
+    position: Option<i32>,
+    options: Option<Vec<String>>,
+    minimum: Option<Option<i32>>,
+    maximum: Option<Option<i32>>,
+}
+
+impl<'m> ApplicationQuestionUpdater<'m> {
+    fn new(question: &'m mut ApplicationQuestion) -> ApplicationQuestionUpdater<'m> {
+        Self {
+            question,
+            label: None,
+            description: None,
+            required: None,
+            position: None,
+            options: None,
+            minimum: None,
+            maximum: None,
+        }
+    }
+
+    /// Directly set the label
+    pub fn override_label(mut self, label: Option<String>) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Directly set the description
+    pub fn override_description(mut self, description: Option<String>) -> Self {
+        self.description = description;
+        self
+    }
+
+    /// Directly set whether the question is required
+    pub fn override_required(mut self, required: Option<bool>) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Directly set the position
+    pub fn override_position(mut self, position: Option<i32>) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Directly set the options
+    pub fn override_options(mut self, options: Option<Vec<String>>) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Directly set the minimum
+    pub fn override_minimum(mut self, minimum: Option<Option<i32>>) -> Self {
+        self.minimum = minimum;
+        self
+    }
+
+    /// Directly set the maximum
+    pub fn override_maximum(mut self, maximum: Option<Option<i32>>) -> Self {
+        self.maximum = maximum;
+        self
+    }
+
+    /// Perform the update
+    #[instrument(
+        name = "ApplicationQuestion::update",
+        skip_all,
+        fields(self.id = %self.question.id, self.event = self.question.event)
+    )]
+    #[allow(clippy::manual_async_fn)]
+    pub fn save<'a, 'c, A>(self, db: A) -> impl Future<Output = Result<()>> + Send + 'a
+    where
+        'm: 'a,
+        A: 'a + Acquire<'c, Database = sqlx::Postgres> + Send,
+    {
+        async move {
+            if self.label.is_none()
+                && self.description.is_none()
+                && self.required.is_none()
+                && self.position.is_none()
+                && self.options.is_none()
+                && self.minimum.is_none()
+                && self.maximum.is_none()
+            {
+                // nothing was changed
+                return Ok(());
+            }
+
+            let mut builder = QueryBuilder::new("UPDATE application_questions SET ");
+            let mut separated = builder.separated(", ");
+
+            if let Some(label) = &self.label {
+                separated.push("label = ");
+                separated.push_bind_unseparated(label);
+            }
+
+            if let Some(description) = &self.description {
+                separated.push("description = ");
+                separated.push_bind_unseparated(description);
+            }
+
+            if let Some(required) = self.required {
+                separated.push("required = ");
+                separated.push_bind_unseparated(required);
+            }
+
+            if let Some(position) = self.position {
+                separated.push("position = ");
+                separated.push_bind_unseparated(position);
+            }
+
+            if let Some(options) = &self.options {
+                separated.push("options = ");
+                separated.push_bind_unseparated(options);
+            }
+
+            if let Some(minimum) = self.minimum {
+                separated.push("minimum = ");
+                separated.push_bind_unseparated(minimum);
+            }
+
+            if let Some(maximum) = self.maximum {
+                separated.push("maximum = ");
+                separated.push_bind_unseparated(maximum);
+            }
+
+            builder.push(" WHERE id = ");
+            builder.push_bind(self.question.id);
+            builder.push(" AND event = ");
+            builder.push_bind(&self.question.event);
+
+            let mut conn = db.acquire().await?;
+            builder.build().execute(&mut *conn).await?;
+
+            if let Some(label) = self.label {
+                self.question.label = label;
+            }
+
+            if let Some(description) = self.description {
+                self.question.description = description;
+            }
+
+            if let Some(required) = self.required {
+                self.question.required = required;
+            }
+
+            if let Some(position) = self.position {
+                self.question.position = position;
+            }
+
+            if let Some(options) = self.options {
+                self.question.options = options;
+            }
+
+            if let Some(minimum) = self.minimum {
+                self.question.minimum = minimum;
+            }
+
+            if let Some(maximum) = self.maximum {
+                self.question.maximum = maximum;
+            }
+
+            Ok(())
+        }
+    }
+}
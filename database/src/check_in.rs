@@ -1,17 +1,57 @@
+#[cfg(feature = "graphql")]
+use crate::stubs::{Participant, User};
 use crate::Result;
+#[cfg(feature = "graphql")]
+use async_graphql::{ComplexObject, Context, Enum, ResultExt, SimpleObject};
 use chrono::{DateTime, Utc};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+#[cfg(feature = "graphql")]
+use serde::Serialize;
 use sqlx::{query, query_as};
 use tracing::instrument;
+use uuid::Uuid;
 
 /// An entry denoting a participant has been checked in
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(Serialize, SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct CheckIn {
     /// The event's unique ID
+    #[cfg_attr(feature = "graphql", graphql(skip))]
     pub event: String,
     /// THe participant's unique ID
+    #[cfg_attr(feature = "graphql", graphql(skip))]
     pub participant_id: i32,
     /// WHen the participant checked in
     pub at: DateTime<Utc>,
+    /// When the participant last checked out, if they are not currently present
+    pub checked_out_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl CheckIn {
+    /// The participant that checked in
+    async fn participant(&self) -> Participant<'_> {
+        Participant::new(self.participant_id, &self.event)
+    }
+
+    /// Whether the participant is currently at the event
+    async fn is_present(&self) -> bool {
+        self.checked_out_at.is_none()
+    }
+
+    /// Every time the participant entered or left the event, oldest first
+    #[instrument(name = "CheckIn::history", skip_all)]
+    async fn history(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<PresenceEntry>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        PresenceEntry::for_participant(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
 }
 
 impl_queries! {
@@ -32,23 +72,137 @@ impl_queries! {
         Ok(result.exists.unwrap_or_default())
     }
 
-    /// Mark a participant as being checked in
+    /// Get a participant's check in
+    #[instrument(name = "CheckIn::find", skip(conn))]
+    pub async fn find(event: &'a str, participant_id: i32; conn) -> Result<Option<CheckIn>> {
+        let mut conn = conn.acquire().await?;
+        let check_in = query_as!(
+            CheckIn,
+            "SELECT * FROM check_ins WHERE participant_id = $1 AND event = $2",
+            participant_id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(check_in)
+    }
+
+    /// Get a participant's check in, locking it until the end of the transaction
+    ///
+    /// Prevents concurrent check ins and check outs from recording the same arrival twice.
+    #[instrument(name = "CheckIn::find_for_update", skip(conn))]
+    pub async fn find_for_update(
+        event: &'a str,
+        participant_id: i32;
+        conn
+    ) -> Result<Option<CheckIn>> {
+        let mut conn = conn.acquire().await?;
+        let check_in = query_as!(
+            CheckIn,
+            "SELECT * FROM check_ins WHERE participant_id = $1 AND event = $2 FOR UPDATE",
+            participant_id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(check_in)
+    }
+
+    /// Get the check ins of everyone currently at an event, in the order they arrived
+    #[instrument(name = "CheckIn::present", skip(conn))]
+    pub async fn present(event: &'a str; conn) -> Result<Vec<CheckIn>> {
+        let mut conn = conn.acquire().await?;
+        let check_ins = query_as!(
+            CheckIn,
+            r#"
+            SELECT * FROM check_ins
+            WHERE event = $1 AND checked_out_at IS NULL
+            ORDER BY at
+            "#,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(check_ins)
+    }
+
+    /// Count the participants who checked in to an event, optionally only those still present
+    #[instrument(name = "CheckIn::count", skip(conn))]
+    pub async fn count(event: &'a str, only_present: bool; conn) -> Result<i64> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            r#"
+            SELECT count(*) FROM check_ins
+            WHERE event = $1 AND (NOT $2 OR checked_out_at IS NULL)
+            "#,
+            event,
+            only_present,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(result.count.unwrap_or_default())
+    }
+
+    /// Record a participant's first check in, returning nothing if they already checked in
+    #[instrument(name = "CheckIn::create", skip(conn))]
+    pub async fn create(
+        event: &'a str,
+        participant_id: i32,
+        at: DateTime<Utc>;
+        conn
+    ) -> Result<Option<CheckIn>> {
+        let mut conn = conn.acquire().await?;
+        let check_in = query_as!(
+            CheckIn,
+            r#"
+            INSERT INTO check_ins (event, participant_id, at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (event, participant_id) DO NOTHING
+            RETURNING *
+            "#,
+            event,
+            participant_id,
+            at,
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(check_in)
+    }
+
+    /// Mark a participant as being checked in at the given time
+    ///
+    /// When the participant already checked in, the earliest arrival is kept. Participants who
+    /// checked out before the given time are marked as present again.
     #[instrument(name = "CheckIn::mark", skip(conn))]
-    pub async fn mark(event: &'a str, participant_id: i32; conn) -> Result<CheckIn> {
+    pub async fn mark(
+        event: &'a str,
+        participant_id: i32,
+        at: DateTime<Utc>;
+        conn
+    ) -> Result<CheckIn> {
         let mut conn = conn.acquire().await?;
         let check_in = query_as!(
             CheckIn,
             r#"
-            INSERT INTO check_ins (event, participant_id)
-            VALUES ($1, $2)
+            INSERT INTO check_ins (event, participant_id, at)
+            VALUES ($1, $2, $3)
             ON CONFLICT (event, participant_id)
                 DO UPDATE SET
-                    event = excluded.event,
-                    participant_id = excluded.participant_id
+                    at = least(check_ins.at, excluded.at),
+                    checked_out_at = CASE
+                        WHEN check_ins.checked_out_at < excluded.at THEN NULL
+                        ELSE check_ins.checked_out_at
+                    END
             RETURNING *
             "#,
             event,
             participant_id,
+            at,
         )
         .fetch_one(&mut *conn)
         .await?;
@@ -56,11 +210,32 @@ impl_queries! {
         Ok(check_in)
     }
 
-    /// Delete a check in
+    /// Mark a participant as having left the event, if they are currently present
+    #[instrument(name = "CheckIn::check_out", skip(conn))]
+    pub async fn check_out(event: &'a str, participant_id: i32; conn) -> Result<Option<CheckIn>> {
+        let mut conn = conn.acquire().await?;
+        let check_in = query_as!(
+            CheckIn,
+            r#"
+            UPDATE check_ins
+            SET checked_out_at = now()
+            WHERE participant_id = $1 AND event = $2 AND checked_out_at IS NULL
+            RETURNING *
+            "#,
+            participant_id,
+            event,
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(check_in)
+    }
+
+    /// Delete a check in, returning whether it existed
     #[instrument(name = "CheckIn::delete", skip(conn))]
-    pub async fn delete(event: &'a str, participant_id: i32; conn) -> Result<()> {
+    pub async fn delete(event: &'a str, participant_id: i32; conn) -> Result<bool> {
         let mut conn = conn.acquire().await?;
-        query!(
+        let result = query!(
             "DELETE FROM check_ins WHERE participant_id = $1 AND event = $2",
             participant_id,
             event
@@ -68,6 +243,122 @@ impl_queries! {
         .execute(&mut *conn)
         .await?;
 
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Whether a participant entered or left the event
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[cfg_attr(feature = "graphql", derive(Enum))]
+#[sqlx(rename_all = "lowercase", type_name = "presence_direction")]
+pub enum PresenceDirection {
+    In,
+    Out,
+}
+
+/// A record of a participant entering or leaving the event
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct PresenceEntry {
+    /// The entry's unique ID
+    pub id: Uuid,
+    /// The slug of the event the participant entered or left
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+    /// The ID of the participant
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub participant_id: i32,
+
+    /// Whether the participant entered or left
+    pub direction: PresenceDirection,
+    /// The ID of the user that recorded the entry, unknown for entries from before it was tracked
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub actor_id: Option<i32>,
+
+    /// When the participant entered or left
+    pub at: DateTime<Utc>,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl PresenceEntry {
+    /// The user that recorded the entry, if known
+    async fn recorded_by(&self) -> Option<User> {
+        self.actor_id.map(|id| User { id })
+    }
+}
+
+impl_queries! {
+    for PresenceEntry;
+
+    /// Record a participant entering or leaving the event
+    #[instrument(name = "PresenceEntry::record", skip(conn))]
+    pub async fn record(
+        event: &'a str,
+        participant_id: i32,
+        direction: PresenceDirection,
+        actor_id: i32,
+        at: DateTime<Utc>;
+        conn
+    ) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            INSERT INTO presence_log (event, participant_id, direction, actor_id, at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            event,
+            participant_id,
+            direction as _,
+            actor_id,
+            at,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get every time a participant entered or left an event, oldest first
+    #[instrument(name = "PresenceEntry::for_participant", skip(conn))]
+    pub async fn for_participant(
+        event: &'a str,
+        participant_id: i32;
+        conn
+    ) -> Result<Vec<PresenceEntry>> {
+        let mut conn = conn.acquire().await?;
+        let entries = query_as!(
+            PresenceEntry,
+            r#"
+            SELECT
+                id, event, participant_id,
+                direction as "direction: PresenceDirection", actor_id, at
+            FROM presence_log
+            WHERE participant_id = $1 AND event = $2
+            ORDER BY at
+            "#,
+            participant_id,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Delete all of a participant's entries for an event
+    #[instrument(name = "PresenceEntry::delete_for_participant", skip(conn))]
+    pub async fn delete_for_participant(event: &'a str, participant_id: i32; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            "DELETE FROM presence_log WHERE participant_id = $1 AND event = $2",
+            participant_id,
+            event
+        )
+        .execute(&mut *conn)
+        .await?;
+
         Ok(())
     }
 }
@@ -0,0 +1,576 @@
+#[cfg(feature = "graphql")]
+use crate::stubs::{Participant, User};
+use crate::Result;
+#[cfg(feature = "graphql")]
+use async_graphql::{
+    connection::{Connection, CursorType, Edge},
+    ComplexObject, Context, ResultExt, SimpleObject,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as, Acquire, QueryBuilder};
+use std::future::Future;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// A place participants are scanned at during an event, like meals, workshops, or swag pickup
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct Checkpoint {
+    /// The checkpoint's unique ID
+    pub id: Uuid,
+    /// The slug of the event the checkpoint is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+
+    /// A unique, URL-safe identifier for the checkpoint within the event
+    pub slug: String,
+    /// A human-readable name for the checkpoint
+    pub name: String,
+    /// The maximum number of different participants that can be scanned, if limited
+    pub capacity: Option<i32>,
+    /// Whether participants can only be scanned once
+    pub once_only: bool,
+
+    /// When the checkpoint was created
+    pub created_at: DateTime<Utc>,
+    /// When the checkpoint was last modified
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl Checkpoint {
+    /// The total number of scans at the checkpoint
+    #[instrument(name = "Checkpoint::scan_count", skip_all)]
+    async fn scan_count(&self, ctx: &Context<'_>) -> async_graphql::Result<i64> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        CheckpointScan::count(self.id, db).await.extend()
+    }
+
+    /// The number of different participants scanned at the checkpoint
+    #[instrument(name = "Checkpoint::participant_count", skip_all)]
+    async fn participant_count(&self, ctx: &Context<'_>) -> async_graphql::Result<i64> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        CheckpointScan::count_participants(self.id, db)
+            .await
+            .extend()
+    }
+
+    /// Get a page of the scans at the checkpoint, newest first
+    #[instrument(name = "Checkpoint::scans", skip(self, ctx))]
+    async fn scans(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        #[graphql(default = 25, validator(minimum = 1, maximum = 100))] first: i32,
+    ) -> async_graphql::Result<Connection<CheckpointScanCursor, CheckpointScan>> {
+        let after = after
+            .as_deref()
+            .map(CheckpointScanCursor::decode_cursor)
+            .transpose()
+            .map_err(async_graphql::Error::new)?;
+
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        let mut scans = CheckpointScan::for_checkpoint(self.id, after, i64::from(first) + 1, db)
+            .await
+            .extend()?;
+
+        let has_next_page = scans.len() > first as usize;
+        scans.truncate(first as usize);
+
+        let mut connection = Connection::new(after.is_some(), has_next_page);
+        connection.edges.extend(
+            scans
+                .into_iter()
+                .map(|scan| Edge::new(CheckpointScanCursor::new(&scan), scan)),
+        );
+
+        Ok(connection)
+    }
+}
+
+impl_queries! {
+    for Checkpoint;
+
+    /// Get all the checkpoints for an event
+    #[instrument(name = "Checkpoint::all", skip(conn))]
+    pub async fn all(event: &'a str; conn) -> Result<Vec<Checkpoint>> {
+        let mut conn = conn.acquire().await?;
+        let checkpoints = query_as!(
+            Checkpoint,
+            "SELECT * FROM checkpoints WHERE event = $1 ORDER BY created_at",
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(checkpoints)
+    }
+
+    /// Find a checkpoint by its ID
+    #[instrument(name = "Checkpoint::find", skip(conn))]
+    pub async fn find(event: &'a str, id: Uuid; conn) -> Result<Option<Checkpoint>> {
+        let mut conn = conn.acquire().await?;
+        let checkpoint = query_as!(
+            Checkpoint,
+            "SELECT * FROM checkpoints WHERE id = $1 AND event = $2",
+            id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(checkpoint)
+    }
+
+    /// Find a checkpoint by its slug
+    #[instrument(name = "Checkpoint::find_by_slug", skip(conn))]
+    pub async fn find_by_slug(event: &'a str, slug: &'a str; conn) -> Result<Option<Checkpoint>> {
+        let mut conn = conn.acquire().await?;
+        let checkpoint = query_as!(
+            Checkpoint,
+            "SELECT * FROM checkpoints WHERE slug = $1 AND event = $2",
+            slug,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(checkpoint)
+    }
+
+    /// Find a checkpoint by its ID, locking it until the end of the transaction
+    ///
+    /// Prevents concurrent scans from exceeding the checkpoint's rules.
+    #[instrument(name = "Checkpoint::find_for_update", skip(conn))]
+    pub async fn find_for_update(event: &'a str, id: Uuid; conn) -> Result<Option<Checkpoint>> {
+        let mut conn = conn.acquire().await?;
+        let checkpoint = query_as!(
+            Checkpoint,
+            "SELECT * FROM checkpoints WHERE id = $1 AND event = $2 FOR UPDATE",
+            id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(checkpoint)
+    }
+
+    /// Create a new checkpoint
+    #[instrument(name = "Checkpoint::create", skip(conn))]
+    pub async fn create(
+        event: &'a str,
+        slug: &'a str,
+        name: &'a str,
+        capacity: Option<i32>,
+        once_only: bool;
+        conn
+    ) -> Result<Checkpoint> {
+        let mut conn = conn.acquire().await?;
+        let checkpoint = query_as!(
+            Checkpoint,
+            r#"
+            INSERT INTO checkpoints (event, slug, name, capacity, once_only)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+            event,
+            slug,
+            name,
+            capacity,
+            once_only,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(checkpoint)
+    }
+
+    /// Delete a checkpoint, along with its scans, returning whether it existed
+    #[instrument(name = "Checkpoint::delete", skip(conn))]
+    pub async fn delete(event: &'a str, id: Uuid; conn) -> Result<bool> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            "DELETE FROM checkpoints WHERE id = $1 AND event = $2",
+            id,
+            event
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete all the checkpoints for an event, along with their scans
+    #[instrument(name = "Checkpoint::delete_for_event", skip(conn))]
+    pub async fn delete_for_event(event: &'a str; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!("DELETE FROM checkpoints WHERE event = $1", event)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl Checkpoint {
+    /// Update the checkpoint's fields
+    pub fn update(&mut self) -> CheckpointUpdater<'_> {
+        CheckpointUpdater::new(self)
+    }
+}
+
+/// Handles updating a checkpoint
+pub struct CheckpointUpdater<'a> {
+    checkpoint: &'a mut Checkpoint,
+    slug: Option<String>,
+    name: Option<String>,
+    capacity: Option<Option<i32>>,
+    once_only: Option<bool>,
+}
+
+impl<'m> CheckpointUpdater<'m> {
+    fn new(checkpoint: &'m mut Checkpoint) -> CheckpointUpdater<'m> {
+        Self {
+            checkpoint,
+            slug: None,
+            name: None,
+            capacity: None,
+            once_only: None,
+        }
+    }
+
+    /// Directly set the slug
+    pub fn override_slug(mut self, slug: Option<String>) -> Self {
+        self.slug = slug;
+        self
+    }
+
+    /// Directly set the name
+    pub fn override_name(mut self, name: Option<String>) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Directly set the capacity
+    pub fn override_capacity(mut self, capacity: Option<Option<i32>>) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Directly set whether participants can only be scanned once
+    pub fn override_once_only(mut self, once_only: Option<bool>) -> Self {
+        self.once_only = once_only;
+        self
+    }
+
+    /// Perform the update
+    #[instrument(
+        name = "Checkpoint::update",
+        skip_all,
+        fields(self.id = %self.checkpoint.id, self.event = self.checkpoint.event)
+    )]
+    #[allow(clippy::manual_async_fn)]
+    pub fn save<'a, 'c, A>(self, db: A) -> impl Future<Output = Result<()>> + Send + 'a
+    where
+        'm: 'a,
+        A: 'a + Acquire<'c, Database = sqlx::Postgres> + Send,
+    {
+        async move {
+            if self.slug.is_none()
+                && self.name.is_none()
+                && self.capacity.is_none()
+                && self.once_only.is_none()
+            {
+                // nothing was changed
+                return Ok(());
+            }
+
+            let mut builder = QueryBuilder::new("UPDATE checkpoints SET ");
+            let mut separated = builder.separated(", ");
+
+            if let Some(slug) = &self.slug {
+                separated.push("slug = ");
+                separated.push_bind_unseparated(slug);
+            }
+
+            if let Some(name) = &self.name {
+                separated.push("name = ");
+                separated.push_bind_unseparated(name);
+            }
+
+            if let Some(capacity) = self.capacity {
+                separated.push("capacity = ");
+                separated.push_bind_unseparated(capacity);
+            }
+
+            if let Some(once_only) = self.once_only {
+                separated.push("once_only = ");
+                separated.push_bind_unseparated(once_only);
+            }
+
+            builder.push(" WHERE id = ");
+            builder.push_bind(self.checkpoint.id);
+            builder.push(" AND event = ");
+            builder.push_bind(&self.checkpoint.event);
+
+            let mut conn = db.acquire().await?;
+            builder.build().execute(&mut *conn).await?;
+
+            if let Some(slug) = self.slug {
+                self.checkpoint.slug = slug;
+            }
+
+            if let Some(name) = self.name {
+                self.checkpoint.name = name;
+            }
+
+            if let Some(capacity) = self.capacity {
+                self.checkpoint.capacity = capacity;
+            }
+
+            if let Some(once_only) = self.once_only {
+                self.checkpoint.once_only = once_only;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// A record of a participant being scanned at a checkpoint
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct CheckpointScan {
+    /// The scan's unique ID
+    pub id: Uuid,
+    /// The slug of the event the scan happened at
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+    /// The ID of the checkpoint the participant was scanned at
+    pub checkpoint_id: Uuid,
+    /// The ID of the participant that was scanned
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub participant_id: i32,
+    /// The ID of the volunteer that scanned the participant
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub scanner_id: i32,
+
+    /// When the participant was scanned
+    pub at: DateTime<Utc>,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl CheckpointScan {
+    /// The participant that was scanned
+    async fn participant(&self) -> Participant<'_> {
+        Participant::new(self.participant_id, &self.event)
+    }
+
+    /// The volunteer that scanned the participant
+    async fn scanned_by(&self) -> User {
+        User {
+            id: self.scanner_id,
+        }
+    }
+}
+
+/// The position of a scan within a checkpoint's history
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CheckpointScanCursor {
+    /// When the participant was scanned
+    pub at: DateTime<Utc>,
+    /// The ID of the scan, used to break ties
+    pub id: Uuid,
+}
+
+impl CheckpointScanCursor {
+    /// Get the position of a scan
+    pub fn new(scan: &CheckpointScan) -> Self {
+        Self {
+            at: scan.at,
+            id: scan.id,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl CursorType for CheckpointScanCursor {
+    type Error = &'static str;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        let (at, id) = s.split_once(':').ok_or("malformed cursor")?;
+
+        let at = at
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or("invalid cursor timestamp")?;
+        let id = id.parse().map_err(|_| "invalid cursor id")?;
+
+        Ok(Self { at, id })
+    }
+
+    fn encode_cursor(&self) -> String {
+        format!("{}:{}", self.at.timestamp_micros(), self.id)
+    }
+}
+
+impl_queries! {
+    for CheckpointScan;
+
+    /// Record a participant being scanned at a checkpoint
+    #[instrument(name = "CheckpointScan::record", skip(conn))]
+    pub async fn record(
+        event: &'a str,
+        checkpoint_id: Uuid,
+        participant_id: i32,
+        scanner_id: i32,
+        at: DateTime<Utc>;
+        conn
+    ) -> Result<CheckpointScan> {
+        let mut conn = conn.acquire().await?;
+        let scan = query_as!(
+            CheckpointScan,
+            r#"
+            INSERT INTO checkpoint_scans (event, checkpoint_id, participant_id, scanner_id, at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+            event,
+            checkpoint_id,
+            participant_id,
+            scanner_id,
+            at,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(scan)
+    }
+
+    /// Check if a participant was scanned at a checkpoint
+    #[instrument(name = "CheckpointScan::exists", skip(conn))]
+    pub async fn exists(checkpoint_id: Uuid, participant_id: i32; conn) -> Result<bool> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            r#"
+            SELECT exists(
+                SELECT 1 FROM checkpoint_scans WHERE checkpoint_id = $1 AND participant_id = $2
+            )
+            "#,
+            checkpoint_id,
+            participant_id,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(result.exists.unwrap_or_default())
+    }
+
+    /// Count the scans at a checkpoint
+    #[instrument(name = "CheckpointScan::count", skip(conn))]
+    pub async fn count(checkpoint_id: Uuid; conn) -> Result<i64> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            "SELECT count(*) FROM checkpoint_scans WHERE checkpoint_id = $1",
+            checkpoint_id
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(result.count.unwrap_or_default())
+    }
+
+    /// Count the different participants scanned at a checkpoint
+    #[instrument(name = "CheckpointScan::count_participants", skip(conn))]
+    pub async fn count_participants(checkpoint_id: Uuid; conn) -> Result<i64> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            r#"
+            SELECT count(DISTINCT participant_id)
+            FROM checkpoint_scans
+            WHERE checkpoint_id = $1
+            "#,
+            checkpoint_id
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(result.count.unwrap_or_default())
+    }
+
+    /// Get a page of the scans at a checkpoint, newest first
+    ///
+    /// Only scans sorting strictly after the cursor are returned.
+    #[instrument(name = "CheckpointScan::for_checkpoint", skip(conn))]
+    pub async fn for_checkpoint(
+        checkpoint_id: Uuid,
+        after: Option<CheckpointScanCursor>,
+        limit: i64;
+        conn
+    ) -> Result<Vec<CheckpointScan>> {
+        let mut conn = conn.acquire().await?;
+        let scans = query_as!(
+            CheckpointScan,
+            r#"
+            SELECT * FROM checkpoint_scans
+            WHERE checkpoint_id = $1
+                AND ($2::timestamptz IS NULL OR (at, id) < ($2, $3))
+            ORDER BY at DESC, id DESC
+            LIMIT $4
+            "#,
+            checkpoint_id,
+            after.map(|after| after.at),
+            after.map(|after| after.id),
+            limit,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(scans)
+    }
+
+    /// Get all the scans of a participant at an event, newest first
+    #[instrument(name = "CheckpointScan::for_participant", skip(conn))]
+    pub async fn for_participant(
+        event: &'a str,
+        participant_id: i32;
+        conn
+    ) -> Result<Vec<CheckpointScan>> {
+        let mut conn = conn.acquire().await?;
+        let scans = query_as!(
+            CheckpointScan,
+            r#"
+            SELECT * FROM checkpoint_scans
+            WHERE participant_id = $1 AND event = $2
+            ORDER BY at DESC
+            "#,
+            participant_id,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(scans)
+    }
+
+    /// Delete all of a participant's scans at an event
+    #[instrument(name = "CheckpointScan::delete_for_participant", skip(conn))]
+    pub async fn delete_for_participant(event: &'a str, participant_id: i32; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            "DELETE FROM checkpoint_scans WHERE participant_id = $1 AND event = $2",
+            participant_id,
+            event
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
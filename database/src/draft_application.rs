@@ -1,21 +1,61 @@
 #[cfg(feature = "graphql")]
 use crate::{
     stubs::{Event, Participant},
-    School,
+    Answer, ApplicationQuestion, Resume, School,
+};
+use crate::{
+    validation::{Fields, Path, ValidationError},
+    Education, Gender, RaceEthnicity, Referrer, Result,
 };
-use crate::{Education, Gender, RaceEthnicity, Referrer, Result};
 #[cfg(feature = "graphql")]
 use async_graphql::{ComplexObject, Context, ResultExt, SimpleObject};
 use chrono::{DateTime, NaiveDate, Utc};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+#[cfg(feature = "graphql")]
+use serde::Serialize;
 use sqlx::{query, query_as, Acquire};
 use std::{fmt::Debug, future::Future};
 use tracing::instrument;
 use uuid::Uuid;
 
+/// Checks whether a field of a draft application has been filled out
+type IsFilled = fn(&DraftApplication) -> bool;
+
+/// The fields that must be filled out before a draft application can be submitted
+///
+/// These mirror the non-null columns of submitted applications.
+const REQUIRED_FIELDS: [(Path, IsFilled); 9] = [
+    (&["input", "gender"], |draft| draft.gender.is_some()),
+    (&["input", "raceEthnicity"], |draft| {
+        draft.race_ethnicity.is_some()
+    }),
+    (&["input", "dateOfBirth"], |draft| {
+        draft.date_of_birth.is_some()
+    }),
+    (&["input", "education"], |draft| draft.education.is_some()),
+    (&["input", "graduationYear"], |draft| {
+        draft.graduation_year.is_some()
+    }),
+    (&["input", "hackathonsAttended"], |draft| {
+        draft.hackathons_attended.is_some()
+    }),
+    (&["input", "addressLine1"], |draft| {
+        draft.address_line1.is_some()
+    }),
+    (&["input", "postalCode"], |draft| {
+        draft.postal_code.is_some()
+    }),
+    (&["input", "country"], |draft| draft.country.is_some()),
+];
+
 /// An in-progress application from a participant
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "graphql", derive(SimpleObject))]
 #[cfg_attr(feature = "graphql", graphql(complex))]
+#[cfg_attr(feature = "graphql", derive(Serialize))]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct DraftApplication {
     /// The slug of the event the application is for
     #[cfg_attr(feature = "graphql", graphql(skip))]
@@ -46,10 +86,13 @@ pub struct DraftApplication {
     /// How many hackathons the participant has attended
     pub hackathons_attended: Option<i32>,
     /// The public VCS URL (i.e. GitHub, GitLab, BitBucket, etc.)
+    #[cfg_attr(feature = "schema", schemars(url))]
     pub vcs_url: Option<String>,
     /// The URL to the participant's portfolio
+    #[cfg_attr(feature = "schema", schemars(url))]
     pub portfolio_url: Option<String>,
     /// The URL to the participant's DevPost profile
+    #[cfg_attr(feature = "schema", schemars(url))]
     pub devpost_url: Option<String>,
 
     /// The first line of the shipping address
@@ -100,9 +143,99 @@ impl DraftApplication {
             None => Ok(None),
         }
     }
+
+    /// The resume uploaded with the application, if any
+    #[instrument(name = "DraftApplication::resume", skip_all)]
+    async fn resume(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Resume>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Resume::find(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
+
+    /// The answers given to the event's application questions
+    #[instrument(name = "DraftApplication::answers", skip_all)]
+    async fn answers(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Answer>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Answer::for_draft(&self.event, self.participant_id, db)
+            .await
+            .extend()
+    }
+
+    /// The required fields that have not been filled out yet
+    async fn missing_fields(&self) -> Vec<String> {
+        self.missing()
+            .into_iter()
+            .filter_map(|mut error| error.field.pop())
+            .collect()
+    }
+
+    /// The required application questions that have not been answered yet
+    #[instrument(name = "DraftApplication::unanswered_questions", skip_all)]
+    async fn unanswered_questions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<ApplicationQuestion>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        let questions = ApplicationQuestion::all(&self.event, db).await.extend()?;
+        let answers = Answer::for_draft(&self.event, self.participant_id, db)
+            .await
+            .extend()?;
+
+        Ok(questions
+            .into_iter()
+            .filter(|question| {
+                question.required
+                    && !answers
+                        .iter()
+                        .any(|answer| answer.question_id == question.id)
+            })
+            .collect())
+    }
+
+    /// Whether all the required fields have been filled out and required questions answered
+    #[instrument(name = "DraftApplication::is_complete", skip_all)]
+    async fn is_complete(&self, ctx: &Context<'_>) -> async_graphql::Result<bool> {
+        if !self.missing().is_empty() {
+            return Ok(false);
+        }
+
+        let questions = self.unanswered_questions(ctx).await?;
+        Ok(questions.is_empty())
+    }
 }
 
 impl DraftApplication {
+    /// Find the required fields that have not been filled out, returning an error for each one
+    pub fn missing(&self) -> Vec<ValidationError> {
+        REQUIRED_FIELDS
+            .into_iter()
+            .filter(|(_, is_filled)| !is_filled(self))
+            .map(|(field, _)| ValidationError::new(field, "is required"))
+            .collect()
+    }
+
+    /// Check the fields that have been filled out, returning an error for every invalid one
+    pub fn validate(&self) -> Vec<ValidationError> {
+        Fields {
+            date_of_birth: self.date_of_birth,
+            graduation_year: self.graduation_year,
+            major: self.major.as_deref(),
+            hackathons_attended: self.hackathons_attended,
+            vcs_url: self.vcs_url.as_deref(),
+            portfolio_url: self.portfolio_url.as_deref(),
+            devpost_url: self.devpost_url.as_deref(),
+            address_line1: self.address_line1.as_deref(),
+            address_line2: self.address_line2.as_deref(),
+            address_line3: self.address_line3.as_deref(),
+            locality: self.locality.as_deref(),
+            administrative_area: self.administrative_area.as_deref(),
+            postal_code: self.postal_code.as_deref(),
+            country: self.country.as_deref(),
+        }
+        .validate()
+    }
+
     /// Create a new draft application
     pub fn new(event: String, participant_id: i32) -> Self {
         Self {
@@ -1,5 +1,5 @@
 use super::Result;
-use sqlx::query_as;
+use sqlx::{query, query_as};
 use tracing::instrument;
 
 /// A temporary table for associating a participant with their email address
@@ -9,6 +9,10 @@ pub struct Email {
     pub participant_id: i32,
     /// The email address to use
     pub address: String,
+    /// The participant's first name, unknown until their details are next synced
+    pub first_name: Option<String>,
+    /// The participant's last name, unknown until their details are next synced
+    pub last_name: Option<String>,
 }
 
 impl_queries! {
@@ -29,23 +33,61 @@ impl_queries! {
         Ok(email)
     }
 
-    /// Associate an email with a participant
+    /// Find the emails for many participants
+    #[instrument(name = "Email::find_many", skip(conn))]
+    pub async fn find_many(ids: &'a [i32]; conn) -> Result<Vec<Email>> {
+        let mut conn = conn.acquire().await?;
+        let emails = query_as!(
+            Email,
+            "SELECT * FROM emails WHERE participant_id = ANY($1)",
+            ids
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(emails)
+    }
+
+    /// Associate an email and name with a participant
     #[instrument(name = "Email::upsert", skip(conn))]
-    pub async fn upsert(id: i32, address: &'a str; conn) -> Result<Email> {
+    pub async fn upsert(
+        id: i32,
+        address: &'a str,
+        first_name: &'a str,
+        last_name: &'a str;
+        conn
+    ) -> Result<Email> {
         let mut conn = conn.acquire().await?;
         let email = query_as!(
             Email,
             r#"
-            INSERT INTO emails VALUES ($1, $2)
-            ON CONFLICT (participant_id) DO UPDATE SET address = excluded.address
+            INSERT INTO emails (participant_id, address, first_name, last_name)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (participant_id) DO UPDATE
+            SET address = excluded.address,
+                first_name = excluded.first_name,
+                last_name = excluded.last_name
             RETURNING *
             "#,
             id,
-            address
+            address,
+            first_name,
+            last_name,
         )
         .fetch_one(&mut *conn)
         .await?;
 
         Ok(email)
     }
+
+    /// Remove a participant's email
+    #[instrument(name = "Email::delete", skip(conn))]
+    pub async fn delete(id: i32; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!("DELETE FROM emails WHERE participant_id = $1", id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,216 @@
+use crate::Result;
+#[cfg(feature = "graphql")]
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{query, query_as};
+use tracing::instrument;
+use uuid::Uuid;
+
+/// The delivery status of an outgoing email
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[cfg_attr(feature = "graphql", derive(Enum))]
+#[sqlx(rename_all = "lowercase", type_name = "email_outbox_status")]
+pub enum OutboxEmailStatus {
+    /// The email is waiting to be delivered
+    Pending,
+    /// The email was delivered
+    Sent,
+    /// Delivery was attempted too many times without succeeding
+    Failed,
+}
+
+/// An email waiting to be, or that was, delivered
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+pub struct OutboxEmail {
+    /// A unique identifier for the email
+    pub id: Uuid,
+    /// The slug of the event the email is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+
+    /// The alias of the template to send
+    pub template: String,
+    /// The variables to render the template with
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub model: Value,
+    /// The address to deliver the email to
+    pub recipient: String,
+
+    /// Whether the email has been delivered
+    pub status: OutboxEmailStatus,
+    /// How many times delivery was attempted
+    pub attempts: i32,
+    /// The reason the most recent delivery attempt failed
+    pub last_error: Option<String>,
+    /// When delivery will next be attempted
+    pub next_attempt_at: DateTime<Utc>,
+    /// When the email was delivered
+    pub sent_at: Option<DateTime<Utc>>,
+
+    /// When the email was queued
+    pub created_at: DateTime<Utc>,
+    /// When the email was last modified
+    pub updated_at: DateTime<Utc>,
+}
+
+impl_queries! {
+    for OutboxEmail;
+
+    /// Queue an email for delivery
+    #[instrument(name = "OutboxEmail::enqueue", skip(conn))]
+    pub async fn enqueue(
+        event: &'a str,
+        template: &'a str,
+        model: &'a Value,
+        recipient: &'a str;
+        conn
+    ) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            INSERT INTO email_outbox (event, template, model, recipient)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            event,
+            template,
+            model,
+            recipient,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Claim the emails that are due for delivery, so no other worker attempts them until the time
+    ///
+    /// Emails locked by other transactions are skipped so that many workers can deliver at once.
+    /// If the worker stops before recording the outcome, the emails are attempted again once the
+    /// claim expires.
+    #[instrument(name = "OutboxEmail::claim_due", skip(conn))]
+    pub async fn claim_due(limit: i64, until: DateTime<Utc>; conn) -> Result<Vec<OutboxEmail>> {
+        let mut conn = conn.acquire().await?;
+        let emails = query_as!(
+            OutboxEmail,
+            r#"
+            UPDATE email_outbox
+            SET next_attempt_at = $2
+            WHERE id IN (
+                SELECT id FROM email_outbox
+                WHERE status = 'pending' AND next_attempt_at <= now()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING
+                id, event, template, model, recipient,
+                status as "status: OutboxEmailStatus", attempts, last_error,
+                next_attempt_at, sent_at, created_at, updated_at
+            "#,
+            limit,
+            until,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(emails)
+    }
+
+    /// Get all the emails for an event that could not be delivered
+    #[instrument(name = "OutboxEmail::failed", skip(conn))]
+    pub async fn failed(event: &'a str; conn) -> Result<Vec<OutboxEmail>> {
+        let mut conn = conn.acquire().await?;
+        let emails = query_as!(
+            OutboxEmail,
+            r#"
+            SELECT
+                id, event, template, model, recipient,
+                status as "status: OutboxEmailStatus", attempts, last_error,
+                next_attempt_at, sent_at, created_at, updated_at
+            FROM email_outbox
+            WHERE status = 'failed' AND event = $1
+            ORDER BY created_at
+            "#,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(emails)
+    }
+
+    /// Record that an email was delivered
+    #[instrument(name = "OutboxEmail::mark_sent", skip(conn))]
+    pub async fn mark_sent(id: Uuid; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            UPDATE email_outbox
+            SET status = 'sent', attempts = attempts + 1, sent_at = now(), last_error = NULL
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record a failed delivery attempt
+    ///
+    /// When no retry time is provided, the email is marked as failed and will not be retried.
+    #[instrument(name = "OutboxEmail::mark_attempt_failed", skip(conn))]
+    pub async fn mark_attempt_failed(
+        id: Uuid,
+        error: &'a str,
+        retry_at: Option<DateTime<Utc>>;
+        conn
+    ) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            UPDATE email_outbox
+            SET
+                status = CASE WHEN $3::timestamptz IS NULL THEN 'failed' ELSE status END,
+                attempts = attempts + 1,
+                last_error = $2,
+                next_attempt_at = coalesce($3, next_attempt_at)
+            WHERE id = $1
+            "#,
+            id,
+            error,
+            retry_at,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Queue a failed email for delivery again
+    #[instrument(name = "OutboxEmail::retry", skip(conn))]
+    pub async fn retry(event: &'a str, id: Uuid; conn) -> Result<Option<OutboxEmail>> {
+        let mut conn = conn.acquire().await?;
+        let email = query_as!(
+            OutboxEmail,
+            r#"
+            UPDATE email_outbox
+            SET status = 'pending', attempts = 0, last_error = NULL, next_attempt_at = now()
+            WHERE status = 'failed' AND id = $1 AND event = $2
+            RETURNING
+                id, event, template, model, recipient,
+                status as "status: OutboxEmailStatus", attempts, last_error,
+                next_attempt_at, sent_at, created_at, updated_at
+            "#,
+            id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(email)
+    }
+}
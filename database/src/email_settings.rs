@@ -0,0 +1,151 @@
+#[cfg(feature = "graphql")]
+use crate::stubs::Event;
+use crate::Result;
+#[cfg(feature = "graphql")]
+use async_graphql::{ComplexObject, SimpleObject};
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as, types::Json, Acquire};
+use std::{collections::HashMap, future::Future};
+use tracing::instrument;
+
+/// How emails for an event are sent
+///
+/// Any settings that are not set fall back to the deployment's defaults.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct EmailSettings {
+    /// The slug of the event the settings are for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+
+    /// The address emails are sent from
+    pub from_address: Option<String>,
+    /// The address replies are sent to
+    pub reply_to: Option<String>,
+    /// The Postmark message stream to send through
+    pub message_stream: Option<String>,
+    /// Overrides for the Postmark template alias used for each template
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub template_aliases: Json<HashMap<String, String>>,
+
+    /// When the settings were created
+    pub created_at: DateTime<Utc>,
+    /// When the settings were last modified
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl EmailSettings {
+    /// The event the settings are for
+    async fn event(&self) -> Event<'_> {
+        Event { slug: &self.event }
+    }
+
+    /// Overrides for the Postmark template alias used for each template
+    async fn template_aliases(&self) -> Vec<TemplateAlias> {
+        let mut aliases = self
+            .template_aliases
+            .iter()
+            .map(|(template, alias)| TemplateAlias {
+                template: template.clone(),
+                alias: alias.clone(),
+            })
+            .collect::<Vec<_>>();
+        aliases.sort_by(|a, b| a.template.cmp(&b.template));
+
+        aliases
+    }
+}
+
+impl EmailSettings {
+    /// Create the default settings for an event
+    pub fn new(event: String) -> Self {
+        Self {
+            event,
+            from_address: None,
+            reply_to: None,
+            message_stream: None,
+            template_aliases: Json(HashMap::new()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Get the Postmark alias to use for a template
+    pub fn alias_for<'t>(&'t self, template: &'t str) -> &'t str {
+        self.template_aliases
+            .get(template)
+            .map(String::as_str)
+            .unwrap_or(template)
+    }
+
+    /// Save the event's email settings
+    #[instrument(name = "EmailSettings::save", skip_all, fields(event = self.event))]
+    #[allow(clippy::manual_async_fn)]
+    pub fn save<'a, 'c, A>(&'a self, db: A) -> impl Future<Output = Result<()>> + Send + 'a
+    where
+        A: 'a + Acquire<'c, Database = sqlx::Postgres> + Send,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            query!(
+                r#"
+                INSERT INTO email_settings (event, from_address, reply_to, message_stream, template_aliases)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (event) DO UPDATE
+                SET from_address = excluded.from_address,
+                    reply_to = excluded.reply_to,
+                    message_stream = excluded.message_stream,
+                    template_aliases = excluded.template_aliases
+                "#,
+                self.event,
+                self.from_address,
+                self.reply_to,
+                self.message_stream,
+                &self.template_aliases as &Json<HashMap<String, String>>,
+            )
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        }
+    }
+}
+
+impl_queries! {
+    for EmailSettings;
+
+    /// Get the email settings for an event, falling back to the defaults
+    #[instrument(name = "EmailSettings::find", skip(conn))]
+    pub async fn find(event: &'a str; conn) -> Result<EmailSettings> {
+        let mut conn = conn.acquire().await?;
+        let settings = query_as!(
+            EmailSettings,
+            r#"
+            SELECT
+                event, from_address, reply_to, message_stream,
+                template_aliases as "template_aliases: Json<HashMap<String, String>>",
+                created_at, updated_at
+            FROM email_settings
+            WHERE event = $1
+            "#,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(settings.unwrap_or_else(|| EmailSettings::new(event.to_owned())))
+    }
+}
+
+/// The Postmark template alias to use in place of one of the portal's templates
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+pub struct TemplateAlias {
+    /// The portal's name for the template
+    pub template: String,
+    /// The alias of the template in Postmark
+    pub alias: String,
+}
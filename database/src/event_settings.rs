@@ -0,0 +1,153 @@
+#[cfg(feature = "graphql")]
+use crate::stubs::Event;
+use crate::Result;
+#[cfg(feature = "graphql")]
+use async_graphql::{ComplexObject, SimpleObject};
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as, Acquire};
+use std::future::Future;
+use tracing::instrument;
+
+/// Organizer-controlled settings for an event
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct EventSettings {
+    /// The slug of the event the settings are for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+
+    /// The name of the event shown to participants, defaults to the event's slug
+    pub name: Option<String>,
+    /// The maximum number of participants that can be accepted, if limited
+    pub capacity: Option<i32>,
+    /// How many days accepted participants have to confirm their attendance, if limited
+    ///
+    /// Participants who have not responded by the deadline are automatically declined.
+    pub rsvp_window_days: Option<i32>,
+    /// Whether participants must confirm their attendance before they can check-in
+    pub check_in_requires_rsvp: bool,
+    /// When participants can no longer change their submitted applications, if limited
+    ///
+    /// Applications can only be changed while they are pending, regardless of the deadline.
+    pub application_edit_deadline: Option<DateTime<Utc>>,
+
+    /// When the settings were created
+    pub created_at: DateTime<Utc>,
+    /// When the settings were last modified
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl EventSettings {
+    /// The event the settings are for
+    async fn event(&self) -> Event<'_> {
+        Event { slug: &self.event }
+    }
+}
+
+impl EventSettings {
+    /// The name of the event shown to participants
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.event)
+    }
+
+    /// Create the default settings for an event
+    pub fn new(event: String) -> Self {
+        Self {
+            event,
+            name: None,
+            capacity: None,
+            rsvp_window_days: None,
+            check_in_requires_rsvp: false,
+            application_edit_deadline: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Save the event's settings
+    #[instrument(name = "EventSettings::save", skip_all, fields(event = self.event))]
+    #[allow(clippy::manual_async_fn)]
+    pub fn save<'a, 'c, A>(&'a self, db: A) -> impl Future<Output = Result<()>> + Send + 'a
+    where
+        A: 'a + Acquire<'c, Database = sqlx::Postgres> + Send,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            query!(
+                r#"
+                INSERT INTO event_settings (
+                    event, name, capacity, rsvp_window_days, check_in_requires_rsvp,
+                    application_edit_deadline
+                )
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (event) DO UPDATE
+                SET name = excluded.name,
+                    capacity = excluded.capacity,
+                    rsvp_window_days = excluded.rsvp_window_days,
+                    check_in_requires_rsvp = excluded.check_in_requires_rsvp,
+                    application_edit_deadline = excluded.application_edit_deadline
+                "#,
+                self.event,
+                self.name,
+                self.capacity,
+                self.rsvp_window_days,
+                self.check_in_requires_rsvp,
+                self.application_edit_deadline,
+            )
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        }
+    }
+}
+
+impl_queries! {
+    for EventSettings;
+
+    /// Get the settings for an event, falling back to the defaults
+    #[instrument(name = "EventSettings::find", skip(conn))]
+    pub async fn find(event: &'a str; conn) -> Result<EventSettings> {
+        let mut conn = conn.acquire().await?;
+        let settings = query_as!(
+            EventSettings,
+            "SELECT * FROM event_settings WHERE event = $1",
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(settings.unwrap_or_else(|| EventSettings::new(event.to_owned())))
+    }
+
+    /// Get the settings for an event, locking them until the end of the transaction
+    ///
+    /// Only events that have saved settings can be locked.
+    #[instrument(name = "EventSettings::find_for_update", skip(conn))]
+    pub async fn find_for_update(event: &'a str; conn) -> Result<Option<EventSettings>> {
+        let mut conn = conn.acquire().await?;
+        let settings = query_as!(
+            EventSettings,
+            "SELECT * FROM event_settings WHERE event = $1 FOR UPDATE",
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(settings)
+    }
+
+    /// Get the slugs of all the events with a limited capacity
+    #[instrument(name = "EventSettings::with_capacity", skip_all)]
+    pub async fn with_capacity(; conn) -> Result<Vec<String>> {
+        let mut conn = conn.acquire().await?;
+        let events = query!("SELECT event FROM event_settings WHERE capacity IS NOT NULL")
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Ok(events.into_iter().map(|row| row.event).collect())
+    }
+}
@@ -0,0 +1,52 @@
+use crate::Result;
+use chrono::{DateTime, Utc};
+use sqlx::query;
+use tracing::instrument;
+
+/// A webhook received from another service, kept to detect replays
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InboundWebhook {
+    /// The sender's unique ID for the webhook
+    pub id: String,
+    /// When the webhook was received
+    pub received_at: DateTime<Utc>,
+}
+
+impl_queries! {
+    for InboundWebhook;
+
+    /// Record that a webhook was received, returning whether it was not seen before
+    ///
+    /// Webhooks received longer ago than the retention period are forgotten, as they would be
+    /// rejected for being too old anyways.
+    #[instrument(name = "InboundWebhook::record", skip(conn))]
+    pub async fn record(id: &'a str, retention_seconds: i64; conn) -> Result<bool> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            "DELETE FROM inbound_webhooks WHERE received_at < now() - make_interval(secs => $1)",
+            retention_seconds as f64,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        let result = query!(
+            "INSERT INTO inbound_webhooks (id) VALUES ($1) ON CONFLICT DO NOTHING",
+            id
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Forget a received webhook, allowing it to be processed again
+    #[instrument(name = "InboundWebhook::forget", skip(conn))]
+    pub async fn forget(id: &'a str; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!("DELETE FROM inbound_webhooks WHERE id = $1", id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
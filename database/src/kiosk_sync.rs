@@ -0,0 +1,101 @@
+use crate::Result;
+use chrono::{DateTime, Utc};
+use sqlx::query;
+use tracing::instrument;
+
+/// A check in or scan recorded offline by a kiosk and synced afterwards
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KioskSyncEntry {
+    /// The slug of the event the entry was recorded at
+    pub event: String,
+    /// The ID of the participant that was checked in or scanned
+    pub participant_id: i32,
+    /// The slug of the checkpoint the participant was scanned at, empty for check ins
+    pub checkpoint: String,
+    /// When the kiosk recorded the entry, according to its own clock
+    pub client_timestamp: DateTime<Utc>,
+
+    /// The ID of the volunteer that synced the entry
+    pub scanner_id: i32,
+
+    /// When the entry was synced
+    pub synced_at: DateTime<Utc>,
+}
+
+impl_queries! {
+    for KioskSyncEntry;
+
+    /// Check if an entry was already synced
+    #[instrument(name = "KioskSyncEntry::exists", skip(conn))]
+    pub async fn exists(
+        event: &'a str,
+        participant_id: i32,
+        checkpoint: &'a str,
+        client_timestamp: DateTime<Utc>;
+        conn
+    ) -> Result<bool> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            r#"
+            SELECT exists(
+                SELECT 1 FROM kiosk_sync_entries
+                WHERE participant_id = $1 AND event = $2
+                    AND checkpoint = $3 AND client_timestamp = $4
+            )
+            "#,
+            participant_id,
+            event,
+            checkpoint,
+            client_timestamp,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(result.exists.unwrap_or_default())
+    }
+
+    /// Record that an entry was synced, returning false if it was already synced before
+    #[instrument(name = "KioskSyncEntry::claim", skip(conn))]
+    pub async fn claim(
+        event: &'a str,
+        participant_id: i32,
+        checkpoint: &'a str,
+        client_timestamp: DateTime<Utc>,
+        scanner_id: i32;
+        conn
+    ) -> Result<bool> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            r#"
+            INSERT INTO kiosk_sync_entries
+                (event, participant_id, checkpoint, client_timestamp, scanner_id)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING
+            "#,
+            event,
+            participant_id,
+            checkpoint,
+            client_timestamp,
+            scanner_id,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete all of a participant's synced entries at an event
+    #[instrument(name = "KioskSyncEntry::delete_for_participant", skip(conn))]
+    pub async fn delete_for_participant(event: &'a str, participant_id: i32; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            "DELETE FROM kiosk_sync_entries WHERE participant_id = $1 AND event = $2",
+            participant_id,
+            event
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -14,20 +14,55 @@ use tracing::{info, instrument, log::LevelFilter};
 
 #[macro_use]
 mod macros;
+mod announcement;
+mod answer;
 mod application;
+mod application_audit;
+mod application_question;
 mod check_in;
+mod checkpoint;
 mod draft_application;
 mod email;
+mod email_outbox;
+mod email_settings;
+mod event_settings;
+mod inbound_webhook;
+mod kiosk_sync;
+mod resume;
+mod review;
+mod rubric_criterion;
 mod school;
 #[cfg(feature = "graphql")]
 mod stubs;
-
-pub use application::{Application, ApplicationStatus, Education, Gender, RaceEthnicity, Referrer};
-pub use check_in::CheckIn;
+mod validation;
+mod webhook_endpoint;
+mod webhook_outbox;
+
+pub use announcement::{Announcement, AnnouncementRecipient, AnnouncementRecipientStatus};
+pub use answer::{Answer, AnswerValue};
+pub use application::{
+    Application, ApplicationCursor, ApplicationFilter, ApplicationOrder, ApplicationStatus,
+    Education, Gender, RaceEthnicity, Referrer,
+};
+pub use application_audit::{ApplicationAuditAction, ApplicationAuditEntry};
+pub use application_question::{ApplicationQuestion, ApplicationQuestionKind};
+pub use check_in::{CheckIn, PresenceDirection, PresenceEntry};
+pub use checkpoint::{Checkpoint, CheckpointScan, CheckpointScanCursor};
 pub use draft_application::DraftApplication;
 pub use email::Email;
+pub use email_outbox::{OutboxEmail, OutboxEmailStatus};
+pub use email_settings::{EmailSettings, TemplateAlias};
+pub use event_settings::EventSettings;
+pub use inbound_webhook::InboundWebhook;
+pub use kiosk_sync::KioskSyncEntry;
+pub use resume::Resume;
+pub use review::{Review, ReviewScore};
+pub use rubric_criterion::RubricCriterion;
 pub use school::School;
-pub use sqlx::{Error as SqlxError, PgPool};
+pub use sqlx::{Error as SqlxError, PgConnection, PgPool};
+pub use validation::ValidationError;
+pub use webhook_endpoint::WebhookEndpoint;
+pub use webhook_outbox::{OutboxWebhook, OutboxWebhookStatus};
 
 pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -0,0 +1,142 @@
+use crate::Result;
+#[cfg(feature = "graphql")]
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use sqlx::query_as;
+use tracing::instrument;
+
+/// A resume uploaded by a participant with their application
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+pub struct Resume {
+    /// The slug of the event the resume was uploaded for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+    /// The ID of the participant that uploaded the resume
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub participant_id: i32,
+    /// Where the resume is located in storage
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub key: String,
+
+    /// The name of the file that was uploaded
+    pub filename: String,
+    /// The MIME type of the file
+    pub content_type: String,
+    /// The size of the file in bytes
+    pub size: i32,
+
+    /// When the resume was first uploaded
+    pub created_at: DateTime<Utc>,
+    /// When the resume was last replaced
+    pub updated_at: DateTime<Utc>,
+}
+
+impl_queries! {
+    for Resume;
+
+    /// Get a participant's resume
+    #[instrument(name = "Resume::find", skip(conn))]
+    pub async fn find(event: &'a str, participant_id: i32; conn) -> Result<Option<Resume>> {
+        let mut conn = conn.acquire().await?;
+        let resume = query_as!(
+            Resume,
+            "SELECT * FROM resumes WHERE participant_id = $1 AND event = $2",
+            participant_id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(resume)
+    }
+
+    /// Save a participant's resume, replacing any existing one
+    #[instrument(name = "Resume::save", skip(conn))]
+    pub async fn save(
+        event: &'a str,
+        participant_id: i32,
+        key: &'a str,
+        filename: &'a str,
+        content_type: &'a str,
+        size: i32;
+        conn
+    ) -> Result<Resume> {
+        let mut conn = conn.acquire().await?;
+        let resume = query_as!(
+            Resume,
+            r#"
+            INSERT INTO resumes (event, participant_id, key, filename, content_type, size)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (participant_id, event)
+            DO UPDATE SET
+                key = excluded.key,
+                filename = excluded.filename,
+                content_type = excluded.content_type,
+                size = excluded.size
+            RETURNING *
+            "#,
+            event,
+            participant_id,
+            key,
+            filename,
+            content_type,
+            size,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(resume)
+    }
+
+    /// Delete a participant's resume, returning it if one existed
+    #[instrument(name = "Resume::delete", skip(conn))]
+    pub async fn delete(event: &'a str, participant_id: i32; conn) -> Result<Option<Resume>> {
+        let mut conn = conn.acquire().await?;
+        let resume = query_as!(
+            Resume,
+            "DELETE FROM resumes WHERE participant_id = $1 AND event = $2 RETURNING *",
+            participant_id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(resume)
+    }
+
+    /// Get the resumes to share with sponsors
+    ///
+    /// Only resumes from participants who opted into sharing their information, and were either
+    /// accepted or checked in, are included.
+    #[instrument(name = "Resume::shareable", skip(conn))]
+    pub async fn shareable(event: &'a str; conn) -> Result<Vec<Resume>> {
+        let mut conn = conn.acquire().await?;
+        let resumes = query_as!(
+            Resume,
+            r#"
+            SELECT resumes.*
+            FROM resumes
+            INNER JOIN applications
+                ON applications.participant_id = resumes.participant_id
+                AND applications.event = resumes.event
+            WHERE resumes.event = $1
+                AND applications.share_information
+                AND (
+                    applications.status IN ('accepted', 'confirmed')
+                    OR EXISTS(
+                        SELECT 1 FROM check_ins
+                        WHERE check_ins.participant_id = resumes.participant_id
+                            AND check_ins.event = resumes.event
+                    )
+                )
+            ORDER BY resumes.participant_id
+            "#,
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(resumes)
+    }
+}
@@ -0,0 +1,253 @@
+use crate::Result;
+#[cfg(feature = "graphql")]
+use crate::{stubs::User, RubricCriterion};
+#[cfg(feature = "graphql")]
+use async_graphql::{ComplexObject, Context, ResultExt, SimpleObject};
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as, Acquire};
+use std::future::Future;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// An organizer's review of an application
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct Review {
+    /// The slug of the event the application is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+    /// The ID of the participant that submitted the application
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub participant_id: i32,
+    /// The ID of the organizer that reviewed the application
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub reviewer_id: i32,
+
+    /// Free-form comments about the application
+    pub comment: String,
+
+    /// When the review was submitted
+    pub created_at: DateTime<Utc>,
+    /// When the review was last modified
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl Review {
+    /// The organizer who wrote the review
+    async fn reviewer(&self) -> User {
+        User {
+            id: self.reviewer_id,
+        }
+    }
+
+    /// The scores given for each rubric criterion
+    #[instrument(name = "Review::scores", skip_all)]
+    async fn scores(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<ReviewScore>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        Review::list_scores(&self.event, self.participant_id, self.reviewer_id, db)
+            .await
+            .extend()
+    }
+}
+
+impl Review {
+    /// Create a new review
+    pub fn new(event: String, participant_id: i32, reviewer_id: i32) -> Self {
+        Self {
+            event,
+            participant_id,
+            reviewer_id,
+            comment: String::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Save the review
+    #[instrument(
+        name = "Review::save",
+        skip_all,
+        fields(
+            event = self.event,
+            participant_id = self.participant_id,
+            reviewer_id = self.reviewer_id
+        )
+    )]
+    #[allow(clippy::manual_async_fn)]
+    pub fn save<'a, 'c, A>(&'a self, db: A) -> impl Future<Output = Result<()>> + Send + 'a
+    where
+        A: 'a + Acquire<'c, Database = sqlx::Postgres> + Send,
+    {
+        async move {
+            let mut conn = db.acquire().await?;
+            query!(
+                r#"
+                INSERT INTO reviews (event, participant_id, reviewer_id, comment)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (participant_id, event, reviewer_id)
+                DO UPDATE SET comment = excluded.comment
+                "#,
+                self.event,
+                self.participant_id,
+                self.reviewer_id,
+                self.comment,
+            )
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        }
+    }
+}
+
+impl_queries! {
+    for Review;
+
+    /// Get a reviewer's review of an application
+    #[instrument(name = "Review::find", skip(conn))]
+    pub async fn find(
+        event: &'a str,
+        participant_id: i32,
+        reviewer_id: i32;
+        conn
+    ) -> Result<Option<Review>> {
+        let mut conn = conn.acquire().await?;
+        let review = query_as!(
+            Review,
+            r#"
+            SELECT * FROM reviews
+            WHERE participant_id = $1 AND event = $2 AND reviewer_id = $3
+            "#,
+            participant_id,
+            event,
+            reviewer_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(review)
+    }
+
+    /// Get the scores given in a review
+    #[instrument(name = "Review::list_scores", skip(conn))]
+    pub async fn list_scores(
+        event: &'a str,
+        participant_id: i32,
+        reviewer_id: i32;
+        conn
+    ) -> Result<Vec<ReviewScore>> {
+        let mut conn = conn.acquire().await?;
+        let scores = query_as!(
+            ReviewScore,
+            r#"
+            SELECT event, criterion_id, score FROM review_scores
+            WHERE participant_id = $1 AND event = $2 AND reviewer_id = $3
+            "#,
+            participant_id,
+            event,
+            reviewer_id
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(scores)
+    }
+
+    /// Set the score given for a criterion in a review
+    #[instrument(name = "Review::set_score", skip(conn))]
+    pub async fn set_score(
+        event: &'a str,
+        participant_id: i32,
+        reviewer_id: i32,
+        criterion_id: Uuid,
+        score: i32;
+        conn
+    ) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            INSERT INTO review_scores (event, participant_id, reviewer_id, criterion_id, score)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (participant_id, event, reviewer_id, criterion_id)
+            DO UPDATE SET score = excluded.score
+            "#,
+            event,
+            participant_id,
+            reviewer_id,
+            criterion_id,
+            score
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Count the number of reviews an application has received
+    #[instrument(name = "Review::count", skip(conn))]
+    pub async fn count(event: &'a str, participant_id: i32; conn) -> Result<i64> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            "SELECT count(*) FROM reviews WHERE participant_id = $1 AND event = $2",
+            participant_id,
+            event
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(result.count.unwrap_or_default())
+    }
+
+    /// Get the average of the total scores given across all reviews of an application
+    #[instrument(name = "Review::average_score", skip(conn))]
+    pub async fn average_score(event: &'a str, participant_id: i32; conn) -> Result<Option<f64>> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            r#"
+            SELECT avg(total)::float8 as average
+            FROM (
+                SELECT sum(score) as total FROM review_scores
+                WHERE participant_id = $1 AND event = $2
+                GROUP BY reviewer_id
+            ) totals
+            "#,
+            participant_id,
+            event
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(result.average)
+    }
+}
+
+/// The score given for a single rubric criterion
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
+pub struct ReviewScore {
+    /// The slug of the event the review is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+    /// The ID of the criterion being scored
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub criterion_id: Uuid,
+    /// The score given
+    pub score: i32,
+}
+
+#[cfg(feature = "graphql")]
+#[ComplexObject]
+impl ReviewScore {
+    /// The criterion being scored
+    #[instrument(name = "ReviewScore::criterion", skip_all)]
+    async fn criterion(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<RubricCriterion>> {
+        let db = ctx.data_unchecked::<sqlx::PgPool>();
+        RubricCriterion::find(&self.event, self.criterion_id, db)
+            .await
+            .extend()
+    }
+}
@@ -0,0 +1,211 @@
+use crate::Result;
+#[cfg(feature = "graphql")]
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as, Acquire, QueryBuilder};
+use std::future::Future;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// A criterion that applications are scored against during review
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+pub struct RubricCriterion {
+    /// The criterion's unique ID
+    pub id: Uuid,
+    /// The slug of the event the criterion is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+
+    /// A short name for the criterion
+    pub name: String,
+    /// Guidance for reviewers on how to score the criterion
+    pub description: String,
+    /// The highest score that can be given, inclusive
+    pub max_score: i32,
+
+    /// When the criterion was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl_queries! {
+    for RubricCriterion;
+
+    /// Get all the rubric criteria for an event
+    #[instrument(name = "RubricCriterion::all", skip(conn))]
+    pub async fn all(event: &'a str; conn) -> Result<Vec<RubricCriterion>> {
+        let mut conn = conn.acquire().await?;
+        let criteria = query_as!(
+            RubricCriterion,
+            "SELECT * FROM rubric_criteria WHERE event = $1 ORDER BY created_at",
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(criteria)
+    }
+
+    /// Find a rubric criterion by its ID
+    #[instrument(name = "RubricCriterion::find", skip(conn))]
+    pub async fn find(event: &'a str, id: Uuid; conn) -> Result<Option<RubricCriterion>> {
+        let mut conn = conn.acquire().await?;
+        let criterion = query_as!(
+            RubricCriterion,
+            "SELECT * FROM rubric_criteria WHERE id = $1 AND event = $2",
+            id,
+            event
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(criterion)
+    }
+
+    /// Create a new rubric criterion
+    #[instrument(name = "RubricCriterion::create", skip(conn))]
+    pub async fn create(
+        event: &'a str,
+        name: &'a str,
+        description: &'a str,
+        max_score: i32;
+        conn
+    ) -> Result<RubricCriterion> {
+        let mut conn = conn.acquire().await?;
+        let criterion = query_as!(
+            RubricCriterion,
+            r#"
+            INSERT INTO rubric_criteria (event, name, description, max_score)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            event,
+            name,
+            description,
+            max_score
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(criterion)
+    }
+
+    /// Delete a rubric criterion, along with any scores given for it
+    #[instrument(name = "RubricCriterion::delete", skip(conn))]
+    pub async fn delete(event: &'a str, id: Uuid; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            "DELETE FROM rubric_criteria WHERE id = $1 AND event = $2",
+            id,
+            event
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
+
+impl RubricCriterion {
+    /// Update the criterion's fields
+    pub fn update(&mut self) -> RubricCriterionUpdater<'_> {
+        RubricCriterionUpdater::new(self)
+    }
+}
+
+/// Handles updating a rubric criterion
+pub struct RubricCriterionUpdater<'a> {
+    criterion: &'a mut RubricCriterion,
+    name: Option<String>,
+    description: Option<String>,
+    max_score: Option<i32>,
+}
+
+impl<'m> RubricCriterionUpdater<'m> {
+    fn new(criterion: &'m mut RubricCriterion) -> RubricCriterionUpdater<'m> {
+        Self {
+            criterion,
+            name: None,
+            description: None,
+            max_score: None,
+        }
+    }
+
+    /// Directly set the name
+    pub fn override_name(mut self, name: Option<String>) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Directly set the description
+    pub fn override_description(mut self, description: Option<String>) -> Self {
+        self.description = description;
+        self
+    }
+
+    /// Directly set the maximum score
+    pub fn override_max_score(mut self, max_score: Option<i32>) -> Self {
+        self.max_score = max_score;
+        self
+    }
+
+    /// Perform the update
+    #[instrument(
+        name = "RubricCriterion::update",
+        skip_all,
+        fields(self.id = %self.criterion.id, self.event = self.criterion.event)
+    )]
+    #[allow(clippy::manual_async_fn)]
+    pub fn save<'a, 'c, A>(self, db: A) -> impl Future<Output = Result<()>> + Send + 'a
+    where
+        'm: 'a,
+        A: 'a + Acquire<'c, Database = sqlx::Postgres> + Send,
+    {
+        async move {
+            if self.name.is_none() && self.description.is_none() && self.max_score.is_none() {
+                // nothing was changed
+                return Ok(());
+            }
+
+            let mut builder = QueryBuilder::new("UPDATE rubric_criteria SET ");
+            let mut separated = builder.separated(", ");
+
+            if let Some(name) = &self.name {
+                separated.push("name = ");
+                separated.push_bind_unseparated(name);
+            }
+
+            if let Some(description) = &self.description {
+                separated.push("description = ");
+                separated.push_bind_unseparated(description);
+            }
+
+            if let Some(max_score) = self.max_score {
+                separated.push("max_score = ");
+                separated.push_bind_unseparated(max_score);
+            }
+
+            builder.push(" WHERE id = ");
+            builder.push_bind(self.criterion.id);
+            builder.push(" AND event = ");
+            builder.push_bind(&self.criterion.event);
+
+            let mut conn = db.acquire().await?;
+            builder.build().execute(&mut *conn).await?;
+
+            if let Some(name) = self.name {
+                self.criterion.name = name;
+            }
+
+            if let Some(description) = self.description {
+                self.criterion.description = description;
+            }
+
+            if let Some(max_score) = self.max_score {
+                self.criterion.max_score = max_score;
+            }
+
+            Ok(())
+        }
+    }
+}
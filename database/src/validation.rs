@@ -0,0 +1,164 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use url::Url;
+
+/// The earliest graduation year that can be given
+const MIN_GRADUATION_YEAR: i32 = 1950;
+/// How many years into the future a graduation year can be
+const MAX_YEARS_UNTIL_GRADUATION: i32 = 10;
+/// The most hackathons a participant can have attended
+const MAX_HACKATHONS_ATTENDED: i32 = 1000;
+/// The longest any free-form text field can be
+const MAX_TEXT_LENGTH: usize = 256;
+
+/// The path to a field in the GraphQL input
+pub(crate) type Path = &'static [&'static str];
+
+/// A problem with one of an application's fields
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationError {
+    /// The path to the field, as it appears in the GraphQL input
+    pub field: Vec<String>,
+    /// Why the field is invalid
+    pub message: String,
+}
+
+impl ValidationError {
+    pub(crate) fn new(field: Path, message: impl ToString) -> Self {
+        Self {
+            field: field.iter().map(|segment| segment.to_string()).collect(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Create an error for one of the items in a list field, identified by the key
+    pub(crate) fn item(field: Path, key: impl ToString, message: impl ToString) -> Self {
+        let mut error = Self::new(field, message);
+        error.field.push(key.to_string());
+        error
+    }
+}
+
+/// The fields shared by draft and submitted applications that need to be checked
+///
+/// Unset fields are skipped, checking for completeness is handled separately.
+pub(crate) struct Fields<'a> {
+    pub date_of_birth: Option<NaiveDate>,
+    pub graduation_year: Option<i32>,
+    pub major: Option<&'a str>,
+    pub hackathons_attended: Option<i32>,
+    pub vcs_url: Option<&'a str>,
+    pub portfolio_url: Option<&'a str>,
+    pub devpost_url: Option<&'a str>,
+    pub address_line1: Option<&'a str>,
+    pub address_line2: Option<&'a str>,
+    pub address_line3: Option<&'a str>,
+    pub locality: Option<&'a str>,
+    pub administrative_area: Option<&'a str>,
+    pub postal_code: Option<&'a str>,
+    pub country: Option<&'a str>,
+}
+
+impl Fields<'_> {
+    /// Check each of the fields, returning an error for every invalid one
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let today = Utc::now().date_naive();
+
+        if let Some(date_of_birth) = self.date_of_birth {
+            if date_of_birth > today {
+                errors.push(ValidationError::new(
+                    &["input", "dateOfBirth"],
+                    "cannot be in the future",
+                ));
+            }
+        }
+
+        if let Some(graduation_year) = self.graduation_year {
+            let max = today.year() + MAX_YEARS_UNTIL_GRADUATION;
+            if !(MIN_GRADUATION_YEAR..=max).contains(&graduation_year) {
+                errors.push(ValidationError::new(
+                    &["input", "graduationYear"],
+                    format!("must be between {MIN_GRADUATION_YEAR} and {max}"),
+                ));
+            }
+        }
+
+        if let Some(hackathons_attended) = self.hackathons_attended {
+            if !(0..=MAX_HACKATHONS_ATTENDED).contains(&hackathons_attended) {
+                errors.push(ValidationError::new(
+                    &["input", "hackathonsAttended"],
+                    format!("must be between 0 and {MAX_HACKATHONS_ATTENDED}"),
+                ));
+            }
+        }
+
+        let urls: [(Path, _); 3] = [
+            (&["input", "vcsUrl"], self.vcs_url),
+            (&["input", "portfolioUrl"], self.portfolio_url),
+            (&["input", "devpostUrl"], self.devpost_url),
+        ];
+        for (field, value) in urls {
+            if let Some(value) = value {
+                if !is_url(value) {
+                    errors.push(ValidationError::new(field, "must be an http(s) url"));
+                }
+            }
+        }
+
+        let text: [(Path, _); 7] = [
+            (&["input", "major"], self.major),
+            (&["input", "addressLine1"], self.address_line1),
+            (&["input", "addressLine2"], self.address_line2),
+            (&["input", "addressLine3"], self.address_line3),
+            (&["input", "locality"], self.locality),
+            (&["input", "administrativeArea"], self.administrative_area),
+            (&["input", "postalCode"], self.postal_code),
+        ];
+        for (field, value) in text {
+            if value.is_some_and(|value| value.chars().count() > MAX_TEXT_LENGTH) {
+                errors.push(ValidationError::new(
+                    field,
+                    format!("must be at most {MAX_TEXT_LENGTH} characters"),
+                ));
+            }
+        }
+
+        if let Some(country) = self.country {
+            if !COUNTRIES.contains(&country) {
+                errors.push(ValidationError::new(
+                    &["input", "country"],
+                    "must be an ISO 3166-1 alpha-2 country code",
+                ));
+            }
+        }
+
+        errors
+    }
+}
+
+/// Check that the value is an absolute http(s) URL
+fn is_url(value: &str) -> bool {
+    Url::parse(value)
+        .map(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+        .unwrap_or_default()
+}
+
+/// The officially assigned ISO 3166-1 alpha-2 country codes
+const COUNTRIES: [&str; 249] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
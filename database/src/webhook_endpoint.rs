@@ -0,0 +1,179 @@
+use crate::Result;
+#[cfg(feature = "graphql")]
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as};
+use tracing::instrument;
+use uuid::Uuid;
+
+/// A URL that the portal delivers an event's webhooks to
+///
+/// Endpoints are only used when webhooks are dispatched by the portal itself rather than through
+/// Svix.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(SimpleObject))]
+pub struct WebhookEndpoint {
+    /// The endpoint's unique ID
+    pub id: Uuid,
+    /// The slug of the event the endpoint is for
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub event: String,
+
+    /// Where webhooks are delivered to
+    pub url: String,
+    /// The secret used to sign the webhooks, prefixed with `whsec_`
+    ///
+    /// Only exposed when it is issued, to limit where it can leak from.
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub secret: String,
+    /// The types of webhooks to deliver, all types are delivered when empty
+    pub event_types: Vec<String>,
+
+    /// When the endpoint was created
+    pub created_at: DateTime<Utc>,
+    /// When the endpoint was last modified
+    pub updated_at: DateTime<Utc>,
+}
+
+impl_queries! {
+    for WebhookEndpoint;
+
+    /// Get all the webhook endpoints for an event
+    #[instrument(name = "WebhookEndpoint::all", skip(conn))]
+    pub async fn all(event: &'a str; conn) -> Result<Vec<WebhookEndpoint>> {
+        let mut conn = conn.acquire().await?;
+        let endpoints = query_as!(
+            WebhookEndpoint,
+            "SELECT * FROM webhook_endpoints WHERE event = $1 ORDER BY created_at",
+            event
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(endpoints)
+    }
+
+    /// Get the endpoints a webhook still needs to be delivered to
+    ///
+    /// Endpoints that are not subscribed to the webhook's type, or that the webhook was already
+    /// delivered to, are excluded.
+    #[instrument(name = "WebhookEndpoint::undelivered", skip(conn))]
+    pub async fn undelivered(
+        event: &'a str,
+        event_type: &'a str,
+        webhook_id: Uuid;
+        conn
+    ) -> Result<Vec<WebhookEndpoint>> {
+        let mut conn = conn.acquire().await?;
+        let endpoints = query_as!(
+            WebhookEndpoint,
+            r#"
+            SELECT webhook_endpoints.*
+            FROM webhook_endpoints
+            WHERE event = $1
+                AND (cardinality(event_types) = 0 OR $2 = ANY(event_types))
+                AND NOT EXISTS(
+                    SELECT 1 FROM webhook_deliveries
+                    WHERE webhook_id = $3 AND endpoint_id = webhook_endpoints.id
+                )
+            ORDER BY created_at
+            "#,
+            event,
+            event_type,
+            webhook_id,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(endpoints)
+    }
+
+    /// Create a new webhook endpoint
+    #[instrument(name = "WebhookEndpoint::create", skip(secret, conn))]
+    pub async fn create(
+        event: &'a str,
+        url: &'a str,
+        secret: &'a str,
+        event_types: &'a [String];
+        conn
+    ) -> Result<WebhookEndpoint> {
+        let mut conn = conn.acquire().await?;
+        let endpoint = query_as!(
+            WebhookEndpoint,
+            r#"
+            INSERT INTO webhook_endpoints (event, url, secret, event_types)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            event,
+            url,
+            secret,
+            event_types,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(endpoint)
+    }
+
+    /// Replace the secret used to sign an endpoint's webhooks
+    #[instrument(name = "WebhookEndpoint::rotate_secret", skip(secret, conn))]
+    pub async fn rotate_secret(
+        event: &'a str,
+        id: Uuid,
+        secret: &'a str;
+        conn
+    ) -> Result<Option<WebhookEndpoint>> {
+        let mut conn = conn.acquire().await?;
+        let endpoint = query_as!(
+            WebhookEndpoint,
+            r#"
+            UPDATE webhook_endpoints
+            SET secret = $3
+            WHERE id = $1 AND event = $2
+            RETURNING *
+            "#,
+            id,
+            event,
+            secret,
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(endpoint)
+    }
+
+    /// Delete a webhook endpoint
+    #[instrument(name = "WebhookEndpoint::delete", skip(conn))]
+    pub async fn delete(event: &'a str, id: Uuid; conn) -> Result<bool> {
+        let mut conn = conn.acquire().await?;
+        let result = query!(
+            "DELETE FROM webhook_endpoints WHERE id = $1 AND event = $2",
+            id,
+            event
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record that a webhook was delivered to the endpoint
+    #[instrument(name = "WebhookEndpoint::mark_delivered", skip(conn))]
+    pub async fn mark_delivered(id: Uuid, webhook_id: Uuid; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            INSERT INTO webhook_deliveries (webhook_id, endpoint_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            webhook_id,
+            id,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{query, query_as};
+use tracing::instrument;
+use uuid::Uuid;
+
+/// The delivery status of an outgoing webhook
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(rename_all = "lowercase", type_name = "webhook_outbox_status")]
+pub enum OutboxWebhookStatus {
+    /// The webhook is waiting to be published
+    Pending,
+    /// The webhook was published
+    Sent,
+    /// Publishing was attempted too many times without succeeding
+    Failed,
+}
+
+/// A webhook waiting to be, or that was, published
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutboxWebhook {
+    /// A unique identifier for the webhook, used to de-duplicate deliveries
+    pub id: Uuid,
+    /// The slug of the event the webhook is for
+    pub event: String,
+
+    /// The type of webhook
+    pub event_type: String,
+    /// The serialized body of the webhook
+    pub payload: Value,
+
+    /// Whether the webhook has been published
+    pub status: OutboxWebhookStatus,
+    /// How many times publishing was attempted
+    pub attempts: i32,
+    /// The reason the most recent attempt failed
+    pub last_error: Option<String>,
+    /// When publishing will next be attempted
+    pub next_attempt_at: DateTime<Utc>,
+    /// When the webhook was published
+    pub sent_at: Option<DateTime<Utc>>,
+
+    /// When the webhook was queued
+    pub created_at: DateTime<Utc>,
+    /// When the webhook was last modified
+    pub updated_at: DateTime<Utc>,
+}
+
+impl_queries! {
+    for OutboxWebhook;
+
+    /// Queue a webhook to be published
+    #[instrument(name = "OutboxWebhook::enqueue", skip(payload, conn))]
+    pub async fn enqueue(event: &'a str, event_type: &'a str, payload: &'a Value; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            "INSERT INTO webhook_outbox (event, event_type, payload) VALUES ($1, $2, $3)",
+            event,
+            event_type,
+            payload,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Claim the webhooks that are due to be published, hiding them from other workers until the
+    /// time
+    ///
+    /// Webhooks being claimed by another worker are skipped. A webhook whose outcome was never
+    /// recorded, such as when the worker crashed, becomes due again once its claim expires.
+    #[instrument(name = "OutboxWebhook::claim_due", skip(conn))]
+    pub async fn claim_due(limit: i64, until: DateTime<Utc>; conn) -> Result<Vec<OutboxWebhook>> {
+        let mut conn = conn.acquire().await?;
+        let webhooks = query_as!(
+            OutboxWebhook,
+            r#"
+            UPDATE webhook_outbox
+            SET next_attempt_at = $2
+            WHERE id IN (
+                SELECT id FROM webhook_outbox
+                WHERE status = 'pending' AND next_attempt_at <= now()
+                ORDER BY created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING
+                id, event, event_type, payload,
+                status as "status: OutboxWebhookStatus", attempts, last_error,
+                next_attempt_at, sent_at, created_at, updated_at
+            "#,
+            limit,
+            until,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(webhooks)
+    }
+
+    /// Record that a webhook was published
+    #[instrument(name = "OutboxWebhook::mark_sent", skip(conn))]
+    pub async fn mark_sent(id: Uuid; conn) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            UPDATE webhook_outbox
+            SET status = 'sent', attempts = attempts + 1, sent_at = now(), last_error = NULL
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record a failed publishing attempt
+    ///
+    /// When no retry time is provided, the webhook is marked as failed and will not be retried.
+    #[instrument(name = "OutboxWebhook::mark_attempt_failed", skip(conn))]
+    pub async fn mark_attempt_failed(
+        id: Uuid,
+        error: &'a str,
+        retry_at: Option<DateTime<Utc>>;
+        conn
+    ) -> Result<()> {
+        let mut conn = conn.acquire().await?;
+        query!(
+            r#"
+            UPDATE webhook_outbox
+            SET
+                status = CASE WHEN $3::timestamptz IS NULL THEN 'failed' ELSE status END,
+                attempts = attempts + 1,
+                last_error = $2,
+                next_attempt_at = coalesce($3, next_attempt_at)
+            WHERE id = $1
+            "#,
+            id,
+            error,
+            retry_at,
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
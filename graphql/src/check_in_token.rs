@@ -0,0 +1,105 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use image::{ImageFormat, Luma};
+use qrcode::{render::svg, EcLevel, QrCode};
+use sha2::Sha256;
+use std::{io::Cursor, sync::Arc};
+
+/// How many bytes of the signature are kept in a token
+///
+/// The signature is truncated to keep tokens short enough to be scanned reliably from a QR code.
+const SIGNATURE_LENGTH: usize = 16;
+/// How many pixels wide each module of a PNG QR code is
+const PNG_MODULE_SIZE: u32 = 8;
+
+/// Issues and verifies the signed tokens participants use to check in to an event
+///
+/// Tokens are of the form `{participant_id}.{signature}` and are only valid for the event they
+/// were issued for.
+#[derive(Clone)]
+pub struct CheckInTokens {
+    key: Arc<[u8]>,
+    public_url: Arc<str>,
+}
+
+impl CheckInTokens {
+    /// Create a new signer using the secret
+    ///
+    /// The public URL is where the portal can be reached from outside, and is used to link to
+    /// the QR codes served by `/check-in-code`.
+    pub fn new(secret: &str, public_url: &str) -> Self {
+        Self {
+            key: secret.as_bytes().into(),
+            public_url: public_url.trim_end_matches('/').into(),
+        }
+    }
+
+    /// Issue a token for a participant to check in to an event
+    pub fn issue(&self, event: &str, participant_id: i32) -> String {
+        let signature = self.mac(event, participant_id).finalize().into_bytes();
+        let signature = URL_SAFE_NO_PAD.encode(&signature[..SIGNATURE_LENGTH]);
+
+        format!("{participant_id}.{signature}")
+    }
+
+    /// Verify a token for an event, returning the ID of the participant it was issued to
+    pub fn verify(&self, event: &str, token: &str) -> Option<i32> {
+        let (participant_id, signature) = token.trim().split_once('.')?;
+        let participant_id = participant_id.parse().ok()?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+        if signature.len() != SIGNATURE_LENGTH {
+            return None;
+        }
+
+        self.mac(event, participant_id)
+            .verify_truncated_left(&signature)
+            .ok()
+            .map(|_| participant_id)
+    }
+
+    /// Get a link to the PNG QR code for a token
+    ///
+    /// Event slugs and tokens only contain URL-safe characters, so they do not need to be escaped.
+    pub(crate) fn qr_code_url(&self, event: &str, token: &str) -> String {
+        format!(
+            "{}/check-in-code?event={event}&token={token}",
+            self.public_url
+        )
+    }
+
+    /// Start the signature for a participant's token
+    fn mac(&self, event: &str, participant_id: i32) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.key).expect("hmac accepts keys of any length");
+        mac.update(format!("{event}.{participant_id}").as_bytes());
+        mac
+    }
+}
+
+/// Render a token as an SVG QR code
+pub(crate) fn render_svg(token: &str) -> String {
+    let code = QrCode::with_error_correction_level(token, EcLevel::M)
+        .expect("tokens always fit in a qr code");
+
+    code.render::<svg::Color<'_>>()
+        .min_dimensions(256, 256)
+        .build()
+}
+
+/// Render a token as a black and white PNG QR code
+///
+/// Email clients do not reliably display SVGs, so this is what gets linked to from emails.
+pub fn render_png(token: &str) -> Vec<u8> {
+    let code = QrCode::with_error_correction_level(token, EcLevel::M)
+        .expect("tokens always fit in a qr code");
+    let image = code
+        .render::<Luma<u8>>()
+        .module_dimensions(PNG_MODULE_SIZE, PNG_MODULE_SIZE)
+        .build();
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .expect("qr codes must encode");
+    png
+}
@@ -9,3 +9,26 @@ impl From<Forbidden> for Error {
         Error::new("forbidden").extend_with(|_, extensions| extensions.set("code", "FORBIDDEN"))
     }
 }
+
+/// An error raised when the webhook portal is requested, but webhooks are dispatched by the portal
+/// itself
+#[derive(Debug)]
+pub struct NoWebhookDashboard;
+
+impl From<NoWebhookDashboard> for Error {
+    fn from(_: NoWebhookDashboard) -> Self {
+        Error::new("no webhook dashboard, endpoints are managed using webhookEndpoints")
+            .extend_with(|_, extensions| extensions.set("code", "NO_WEBHOOK_DASHBOARD"))
+    }
+}
+
+/// An error raised when a file could not be stored or retrieved
+#[derive(Debug)]
+pub struct StorageFailed(pub std::io::Error);
+
+impl From<StorageFailed> for Error {
+    fn from(StorageFailed(error): StorageFailed) -> Self {
+        tracing::error!(%error, "unexpected storage error");
+        Error::new("internal server error")
+    }
+}
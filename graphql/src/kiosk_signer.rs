@@ -0,0 +1,50 @@
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use std::{
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+
+/// Signs the snapshots kiosks use while offline
+///
+/// Snapshots are signed with an Ed25519 key, so kiosks only need the public key to verify that a
+/// snapshot was issued by the portal and was not modified.
+#[derive(Clone)]
+pub struct KioskSigner {
+    key: Arc<Ed25519KeyPair>,
+}
+
+impl KioskSigner {
+    /// Create a new signer from a base64-encoded 32-byte Ed25519 seed
+    pub fn new(seed: &str) -> Result<Self, InvalidSigningKey> {
+        let seed = STANDARD.decode(seed).map_err(|_| InvalidSigningKey)?;
+        let key = Ed25519KeyPair::from_seed_unchecked(&seed).map_err(|_| InvalidSigningKey)?;
+
+        Ok(Self { key: Arc::new(key) })
+    }
+
+    /// Sign a serialized snapshot, returning the URL-safe base64-encoded signature
+    pub fn sign(&self, snapshot: &[u8]) -> String {
+        URL_SAFE_NO_PAD.encode(self.key.sign(snapshot))
+    }
+
+    /// The URL-safe base64-encoded public key that snapshots can be verified with
+    pub fn public_key(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.key.public_key())
+    }
+}
+
+/// The kiosk signing key is not a base64-encoded 32-byte seed
+#[derive(Debug)]
+pub struct InvalidSigningKey;
+
+impl Display for InvalidSigningKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "signing key must be a base64-encoded 32-byte seed")
+    }
+}
+
+impl std::error::Error for InvalidSigningKey {}
@@ -1,19 +1,28 @@
 use async_graphql::{
-    extensions::Analyzer, EmptySubscription, SDLExportOptions, Schema as BaseSchema, SchemaBuilder,
+    extensions::Analyzer, http::MultipartOptions, EmptySubscription, SDLExportOptions,
+    Schema as BaseSchema, SchemaBuilder,
 };
 use database::PgPool;
-use std::sync::Arc;
-use svix::api::Svix;
 
+pub mod check_in_token;
 mod errors;
+pub mod kiosk_signer;
 mod mutation;
+mod notify;
 mod query;
-mod webhooks;
+pub mod resume_book;
+pub mod rsvp;
+pub mod storage;
+pub mod waitlist;
+pub mod webhooks;
 
 use mutation::Mutation;
 use query::Query;
 #[cfg(feature = "schema")]
-pub use webhooks::Payload;
+pub use webhooks::{Deleted, Payload, StatusChanged};
+
+/// The largest request body accepted, leaving room for the operation around an uploaded resume
+pub const MAX_REQUEST_SIZE: usize = mutation::MAX_RESUME_SIZE as usize + 64 * 1024;
 
 /// The graphql schema for the service
 pub type Schema = BaseSchema<Query, Mutation, EmptySubscription>;
@@ -27,8 +36,29 @@ fn builder() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
 }
 
 /// Build the schema with the necessary data
-pub fn schema(db: PgPool, mail: mail::Client, svix: Svix) -> Schema {
-    builder().data(db).data(mail).data(Arc::new(svix)).finish()
+pub fn schema(
+    db: PgPool,
+    webhooks: webhooks::Client,
+    storage: storage::Storage,
+    tokens: check_in_token::CheckInTokens,
+    resume_book_links: resume_book::ResumeBookLinks,
+    kiosk_signer: kiosk_signer::KioskSigner,
+) -> Schema {
+    builder()
+        .data(db)
+        .data(webhooks)
+        .data(storage)
+        .data(tokens)
+        .data(resume_book_links)
+        .data(kiosk_signer)
+        .finish()
+}
+
+/// The limits for multipart requests, which only need to carry a single resume
+pub fn multipart_options() -> MultipartOptions {
+    MultipartOptions::default()
+        .max_file_size(mutation::MAX_RESUME_SIZE as usize)
+        .max_num_files(1)
 }
 
 /// Export the GraphQL schema
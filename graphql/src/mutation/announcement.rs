@@ -0,0 +1,84 @@
+use super::{results, UserError};
+use async_graphql::{Context, InputObject, Object, Result, ResultExt};
+use context::{checks, UserRole};
+use database::{Announcement, ApplicationFilter, PgPool};
+use tracing::instrument;
+
+results! {
+    SendAnnouncementResult {
+        /// The announcement that was queued for delivery
+        announcement: Announcement,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "AnnouncementMutation")]
+impl Mutation {
+    /// Broadcast an announcement to every participant matching the segment
+    ///
+    /// The announcement is delivered in the background, its recipients can be used to track
+    /// delivery.
+    #[instrument(name = "Mutation::send_announcement", skip(self, ctx))]
+    async fn send_announcement(
+        &self,
+        ctx: &Context<'_>,
+        input: SendAnnouncementInput,
+    ) -> Result<SendAnnouncementResult> {
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let mut errors = Vec::new();
+        if input.subject.trim().is_empty() {
+            errors.push(UserError::new(&["input", "subject"], "cannot be empty"));
+        }
+        if input.body.trim().is_empty() {
+            errors.push(UserError::new(&["input", "body"], "cannot be empty"));
+        }
+        if !errors.is_empty() {
+            return Ok(errors.into());
+        }
+
+        let segment = input.segment.unwrap_or_default();
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let mut txn = db.begin().await?;
+
+        let count = Announcement::count_segment(&scope.event, &segment, &mut txn)
+            .await
+            .extend()?;
+        if count == 0 {
+            return Ok(
+                UserError::new(&["input", "segment"], "no participants match the segment").into(),
+            );
+        }
+
+        let announcement = Announcement::create(
+            &scope.event,
+            user.id,
+            &input.subject,
+            &input.body,
+            &segment,
+            &mut txn,
+        )
+        .await
+        .extend()?;
+
+        txn.commit().await?;
+
+        Ok(announcement.into())
+    }
+}
+
+/// Input fields for sending an announcement
+#[derive(Debug, InputObject)]
+struct SendAnnouncementInput {
+    /// The subject line of the announcement
+    subject: String,
+    /// The content of the announcement
+    body: String,
+    /// The conditions participants must match to receive the announcement, defaults to everyone
+    segment: Option<ApplicationFilter>,
+}
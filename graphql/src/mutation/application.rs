@@ -1,11 +1,26 @@
-use super::{results, UserError};
-use crate::webhooks;
-use async_graphql::{Context, ErrorExtensions, InputObject, Object, Result, ResultExt};
+use super::{results, set_option, UserError};
+use crate::{
+    check_in_token::CheckInTokens,
+    notify,
+    storage::Storage,
+    waitlist,
+    webhooks::{self, Deleted, StatusChanged},
+};
+use async_graphql::{
+    Context, InputObject, MaybeUndefined, Object, Result, ResultExt, SimpleObject,
+};
+use chrono::{NaiveDate, Utc};
 use context::{checks, UserRole};
-use database::{Application, ApplicationStatus, DraftApplication, Email, PgPool};
-use std::sync::Arc;
-use svix::api::Svix;
-use tracing::{error, instrument};
+use database::{
+    Answer, Application, ApplicationAuditAction, ApplicationAuditEntry, ApplicationFilter,
+    ApplicationOrder, ApplicationQuestion, ApplicationStatus, CheckIn, CheckpointScan,
+    DraftApplication, Education, Email, EventSettings, Gender, KioskSyncEntry, PgPool,
+    PresenceEntry, RaceEthnicity, Referrer, Resume, School,
+};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use tracing::{error, instrument, warn};
+use uuid::Uuid;
 
 results! {
     SubmitApplicationResult {
@@ -20,6 +35,26 @@ results! {
         /// The updated application
         application: Application,
     }
+    PromoteFromWaitlistResult {
+        /// The applications that were accepted
+        applications: Vec<Application>,
+    }
+    RsvpResult {
+        /// The updated application
+        application: Application,
+    }
+    WithdrawApplicationResult {
+        /// The withdrawn application
+        application: Application,
+    }
+    UpdateSubmittedApplicationResult {
+        /// The updated application
+        application: Application,
+    }
+    DeleteApplicationResult {
+        /// The ID of the deleted application/participant
+        deleted_id: i32,
+    }
 }
 
 #[derive(Default)]
@@ -35,6 +70,7 @@ impl Mutation {
         checks::has_role(ctx, UserRole::Participant)?;
 
         let db = ctx.data_unchecked::<PgPool>();
+        let tokens = ctx.data_unchecked::<CheckInTokens>();
         let mut txn = db.begin().await?;
         if Application::exists(&scope.event, user.id, &mut txn)
             .await
@@ -45,49 +81,328 @@ impl Mutation {
             );
         }
 
-        let application = match Application::from_draft(&scope.event, user.id, &mut txn).await {
-            Ok(application) => application,
-            Err(err) => {
-                match err.as_ref() {
-                    database::SqlxError::Database(err) => {
-                        if matches!(err.code().as_deref(), Some("23502")) {
-                            return Ok(UserError::new(
-                                &["submitApplication"],
-                                "application is incomplete",
-                            )
-                            .into());
-                        }
-                    }
-                    database::SqlxError::RowNotFound => {
-                        return Ok(UserError::new(
-                            &["submitApplication"],
-                            "could not find a draft application",
-                        )
-                        .into())
-                    }
-                    _ => {}
-                }
+        let Some(draft) = DraftApplication::find(&scope.event, user.id, &mut txn)
+            .await
+            .extend()?
+        else {
+            return Ok(UserError::new(
+                &["submitApplication"],
+                "could not find a draft application",
+            )
+            .into());
+        };
+
+        let questions = ApplicationQuestion::all(&scope.event, &mut txn)
+            .await
+            .extend()?;
+        let answers = Answer::for_draft(&scope.event, user.id, &mut txn)
+            .await
+            .extend()?;
+
+        let mut errors = draft.missing();
+        errors.extend(draft.validate());
+        errors.extend(ApplicationQuestion::validate_answers(
+            &questions, &answers, true,
+        ));
+        if !errors.is_empty() {
+            return Ok(UserError::from_validation(errors).into());
+        }
+
+        let application = Application::from_draft(&scope.event, user.id, &mut txn)
+            .await
+            .extend()?;
+        Answer::copy_from_draft(&scope.event, user.id, &mut txn)
+            .await
+            .extend()?;
+
+        DraftApplication::delete(&scope.event, user.id, &mut txn)
+            .await
+            .extend()?;
+        notify::application_status(&mut txn, tokens, &application, &user.email)
+            .await
+            .extend()?;
+        webhooks::enqueue(
+            &mut txn,
+            "application.submitted",
+            &scope.event,
+            &application,
+        )
+        .await
+        .extend()?;
 
-                return Err(err.extend());
+        txn.commit().await?;
+
+        Ok(application.into())
+    }
+
+    /// Confirm or decline attendance to the event after being accepted
+    ///
+    /// Declining frees up a slot, which is filled from the event's waitlist.
+    #[instrument(name = "Mutation::rsvp", skip(self, ctx))]
+    async fn rsvp(&self, ctx: &Context<'_>, attending: bool) -> Result<RsvpResult> {
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_role(ctx, UserRole::Participant)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let tokens = ctx.data_unchecked::<CheckInTokens>();
+        let mut txn = db.begin().await?;
+
+        let Some(mut application) = Application::find(&scope.event, user.id, &mut txn)
+            .await
+            .extend()?
+        else {
+            return Ok(UserError::new(&["rsvp"], "application not found").into());
+        };
+
+        if application.status != ApplicationStatus::Accepted {
+            return Ok(UserError::new(&["rsvp"], "only accepted applications can rsvp").into());
+        }
+
+        let deadline = Application::find_rsvp_deadline(&scope.event, user.id, &mut txn)
+            .await
+            .extend()?;
+        if deadline.is_some_and(|deadline| deadline < Utc::now()) {
+            return Ok(UserError::new(&["rsvp"], "the rsvp deadline has passed").into());
+        }
+
+        let status = match attending {
+            true => ApplicationStatus::Confirmed,
+            false => ApplicationStatus::Declined,
+        };
+        application
+            .update()
+            .status(status)
+            .save(&mut txn)
+            .await
+            .extend()?;
+        notify::application_status(&mut txn, tokens, &application, &user.email)
+            .await
+            .extend()?;
+        webhooks::enqueue(
+            &mut txn,
+            "application.status_changed",
+            &scope.event,
+            &StatusChanged {
+                previous_status: ApplicationStatus::Accepted,
+                application: &application,
+            },
+        )
+        .await
+        .extend()?;
+
+        txn.commit().await?;
+
+        if !attending {
+            if let Err(error) = waitlist::promote(db, tokens, &scope.event, None).await {
+                error!(%error, "failed to promote from waitlist");
             }
+        }
+
+        Ok(application.into())
+    }
+
+    /// Withdraw a submitted application
+    ///
+    /// Applications can be withdrawn until the participant declines or the application is
+    /// rejected. Withdrawing an accepted application frees up a slot, which is filled from the
+    /// event's waitlist.
+    #[instrument(name = "Mutation::withdraw_application", skip_all)]
+    async fn withdraw_application(&self, ctx: &Context<'_>) -> Result<WithdrawApplicationResult> {
+        const FIELD: &[&str] = &["withdrawApplication"];
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_role(ctx, UserRole::Participant)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let tokens = ctx.data_unchecked::<CheckInTokens>();
+        let mut txn = db.begin().await?;
+
+        let Some(mut application) = Application::find(&scope.event, user.id, &mut txn)
+            .await
+            .extend()?
+        else {
+            return Ok(UserError::new(FIELD, "application not found").into());
         };
 
-        DraftApplication::delete(&scope.event, user.id, &mut txn)
+        let previous_status = application.status;
+        if !matches!(
+            previous_status,
+            ApplicationStatus::Pending
+                | ApplicationStatus::Waitlisted
+                | ApplicationStatus::Accepted
+                | ApplicationStatus::Confirmed
+        ) {
+            return Ok(UserError::new(FIELD, "application can no longer be withdrawn").into());
+        }
+
+        application
+            .update()
+            .status(ApplicationStatus::Withdrawn)
+            .save(&mut txn)
+            .await
+            .extend()?;
+        ApplicationAuditEntry::record(
+            &scope.event,
+            user.id,
+            user.id,
+            ApplicationAuditAction::Withdrawn,
+            &json!({
+                "status": {
+                    "from": previous_status.to_str(),
+                    "to": ApplicationStatus::Withdrawn.to_str(),
+                },
+            }),
+            &mut txn,
+        )
+        .await
+        .extend()?;
+        notify::application_status(&mut txn, tokens, &application, &user.email)
             .await
             .extend()?;
+        webhooks::enqueue(
+            &mut txn,
+            "application.status_changed",
+            &scope.event,
+            &StatusChanged {
+                previous_status,
+                application: &application,
+            },
+        )
+        .await
+        .extend()?;
 
         txn.commit().await?;
 
-        let svix = ctx.data_unchecked::<Arc<Svix>>();
-        webhooks::send(svix, "application.submitted", &scope.event, &application).await;
+        if matches!(
+            previous_status,
+            ApplicationStatus::Accepted | ApplicationStatus::Confirmed
+        ) {
+            if let Err(error) = waitlist::promote(db, tokens, &scope.event, None).await {
+                error!(%error, "failed to promote from waitlist");
+            }
+        }
 
-        let mail = ctx.data_unchecked::<mail::Client>().clone();
-        let email = user.email.clone();
-        tokio::task::spawn(async move {
-            if let Err(error) = mail.send_templated("pending", &email).await {
-                error!(%error, "failed to send email")
+        Ok(application.into())
+    }
+
+    /// Change the details of a submitted application
+    ///
+    /// Applications can only be changed while they are pending and before the event's edit
+    /// deadline, if it has one.
+    #[instrument(name = "Mutation::update_submitted_application", skip_all)]
+    async fn update_submitted_application(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateSubmittedApplicationInput,
+    ) -> Result<UpdateSubmittedApplicationResult> {
+        const FIELD: &[&str] = &["updateSubmittedApplication"];
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_role(ctx, UserRole::Participant)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let mut txn = db.begin().await?;
+
+        let Some(mut application) = Application::find(&scope.event, user.id, &mut txn)
+            .await
+            .extend()?
+        else {
+            return Ok(UserError::new(FIELD, "application not found").into());
+        };
+
+        if application.status != ApplicationStatus::Pending {
+            return Ok(UserError::new(FIELD, "only pending applications can be changed").into());
+        }
+
+        let settings = EventSettings::find(&scope.event, &mut txn).await.extend()?;
+        if settings
+            .application_edit_deadline
+            .is_some_and(|deadline| deadline < Utc::now())
+        {
+            return Ok(UserError::new(FIELD, "the edit deadline has passed").into());
+        }
+
+        let previous = application.clone();
+
+        match input.school_id {
+            MaybeUndefined::Value(school_id) => {
+                if School::exists(&school_id, &mut txn).await.extend()? {
+                    application.school_id = Some(school_id);
+                } else {
+                    return Ok(UserError::new(&["input", "schoolId"], "unknown school").into());
+                }
             }
-        });
+            MaybeUndefined::Null => application.school_id = None,
+            MaybeUndefined::Undefined => {}
+        }
+
+        if let Some(gender) = input.gender {
+            application.gender = gender;
+        }
+        if let Some(race_ethnicity) = input.race_ethnicity {
+            application.race_ethnicity = race_ethnicity;
+        }
+        if let Some(date_of_birth) = input.date_of_birth {
+            application.date_of_birth = date_of_birth;
+        }
+        set_option!(input.referrer => application.referrer);
+        if let Some(education) = input.education {
+            application.education = education;
+        }
+        if let Some(graduation_year) = input.graduation_year {
+            application.graduation_year = graduation_year;
+        }
+        set_option!(input.major => application.major);
+        if let Some(hackathons_attended) = input.hackathons_attended {
+            application.hackathons_attended = hackathons_attended;
+        }
+        set_option!(input.vcs_url => application.vcs_url);
+        set_option!(input.portfolio_url => application.portfolio_url);
+        set_option!(input.devpost_url => application.devpost_url);
+        if let Some(address_line1) = input.address_line1 {
+            application.address_line1 = address_line1;
+        }
+        set_option!(input.address_line2 => application.address_line2);
+        set_option!(input.address_line3 => application.address_line3);
+        set_option!(input.locality => application.locality);
+        set_option!(input.administrative_area => application.administrative_area);
+        if let Some(postal_code) = input.postal_code {
+            application.postal_code = postal_code;
+        }
+        if let Some(country) = input.country {
+            application.country = country;
+        }
+        if let Some(share_information) = input.share_information {
+            application.share_information = share_information;
+        }
+
+        let errors = application.validate();
+        if !errors.is_empty() {
+            return Ok(UserError::from_validation(errors).into());
+        }
+
+        let changes = changed_fields(&previous, &application);
+        if changes.is_empty() {
+            return Ok(application.into());
+        }
+
+        application.save_details(&mut txn).await.extend()?;
+        ApplicationAuditEntry::record(
+            &scope.event,
+            user.id,
+            user.id,
+            ApplicationAuditAction::Updated,
+            &Value::Object(changes),
+            &mut txn,
+        )
+        .await
+        .extend()?;
+        webhooks::enqueue(&mut txn, "application.updated", &scope.event, &application)
+            .await
+            .extend()?;
+
+        txn.commit().await?;
 
         Ok(application.into())
     }
@@ -106,7 +421,9 @@ impl Mutation {
         checks::has_at_least_role(ctx, UserRole::Organizer)?;
 
         let db = ctx.data_unchecked::<PgPool>();
-        let Some(mut application) = Application::find(&scope.event, input.id, db)
+        let mut txn = db.begin().await?;
+
+        let Some(mut application) = Application::find(&scope.event, input.id, &mut txn)
             .await
             .extend()?
         else {
@@ -117,13 +434,85 @@ impl Mutation {
             .update()
             .override_flagged(input.flagged)
             .override_notes(input.notes)
-            .save(db)
+            .save(&mut txn)
+            .await
+            .extend()?;
+        webhooks::enqueue(&mut txn, "application.updated", &scope.event, &application)
             .await
             .extend()?;
 
+        txn.commit().await?;
+
         Ok(application.into())
     }
 
+    /// Delete an application, along with the participant's check in and checkpoint scans
+    ///
+    /// If the application was accepted, the freed slot is filled from the event's waitlist.
+    #[instrument(name = "Mutation::delete_application", skip(self, ctx))]
+    async fn delete_application(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<DeleteApplicationResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let tokens = ctx.data_unchecked::<CheckInTokens>();
+        let mut txn = db.begin().await?;
+
+        let Some(application) = Application::find(&scope.event, id, &mut txn)
+            .await
+            .extend()?
+        else {
+            return Ok(UserError::new(&["id"], "application not found").into());
+        };
+
+        Application::delete(&scope.event, id, &mut txn)
+            .await
+            .extend()?;
+        CheckIn::delete(&scope.event, id, &mut txn).await.extend()?;
+        CheckpointScan::delete_for_participant(&scope.event, id, &mut txn)
+            .await
+            .extend()?;
+        PresenceEntry::delete_for_participant(&scope.event, id, &mut txn)
+            .await
+            .extend()?;
+        KioskSyncEntry::delete_for_participant(&scope.event, id, &mut txn)
+            .await
+            .extend()?;
+        let resume = Resume::delete(&scope.event, id, &mut txn).await.extend()?;
+        webhooks::enqueue(
+            &mut txn,
+            "application.deleted",
+            &scope.event,
+            &Deleted { participant_id: id },
+        )
+        .await
+        .extend()?;
+
+        txn.commit().await?;
+
+        if let Some(resume) = resume {
+            let storage = ctx.data_unchecked::<Storage>();
+            if let Err(error) = storage.delete(&resume.key).await {
+                warn!(%error, key = resume.key, "failed to remove deleted resume");
+            }
+        }
+
+        if matches!(
+            application.status,
+            ApplicationStatus::Accepted | ApplicationStatus::Confirmed
+        ) {
+            if let Err(error) = waitlist::promote(db, tokens, &scope.event, None).await {
+                error!(%error, "failed to promote from waitlist");
+            }
+        }
+
+        Ok(id.into())
+    }
+
     /// Change an application's status
     ///
     /// The following transitions are allowed:
@@ -131,6 +520,12 @@ impl Mutation {
     /// - WAITLISTED -> ACCEPTED, REJECTED
     /// - ACCEPTED   -> ()
     /// - REJECTED   -> ()
+    /// - CONFIRMED  -> ()
+    /// - DECLINED   -> ()
+    /// - WITHDRAWN  -> ()
+    ///
+    /// Only participants can move their application to CONFIRMED or DECLINED using `rsvp`, or to
+    /// WITHDRAWN using `withdrawApplication`.
     #[instrument(name = "Mutation::change_application_status", skip(self, ctx))]
     async fn change_application_status(
         &self,
@@ -141,6 +536,7 @@ impl Mutation {
         checks::has_at_least_role(ctx, UserRole::Organizer)?;
 
         let db = ctx.data_unchecked::<PgPool>();
+        let tokens = ctx.data_unchecked::<CheckInTokens>();
         let mut txn = db.begin().await?;
 
         let Some(mut application) = Application::find(&scope.event, input.id, &mut txn)
@@ -150,18 +546,13 @@ impl Mutation {
             return Ok(UserError::new(&["id"], "application not found").into());
         };
 
-        if matches!(
-            (application.status, input.status),
-            (_, ApplicationStatus::Pending)
-                | (ApplicationStatus::Waitlisted, ApplicationStatus::Waitlisted)
-                | (ApplicationStatus::Accepted, _)
-                | (ApplicationStatus::Rejected, _)
-        ) {
+        if !is_valid_transition(application.status, input.status) {
             return Ok(
                 UserError::new(&["status"], "invalid status transition for application").into(),
             );
         }
 
+        let previous_status = application.status;
         application
             .update()
             .status(input.status)
@@ -173,21 +564,217 @@ impl Mutation {
             .await
             .extend()?
             .expect("email must exist");
+        notify::application_status(&mut txn, tokens, &application, &email.address)
+            .await
+            .extend()?;
+        webhooks::enqueue(
+            &mut txn,
+            "application.status_changed",
+            &scope.event,
+            &StatusChanged {
+                previous_status,
+                application: &application,
+            },
+        )
+        .await
+        .extend()?;
 
-        let mail = ctx.data_unchecked::<mail::Client>().clone();
-        tokio::task::spawn(async move {
-            if let Err(error) = mail
-                .send_templated(input.status.to_str(), &email.address)
-                .await
-            {
-                error!(%error, "failed to send email")
+        txn.commit().await?;
+
+        Ok(application.into())
+    }
+
+    /// Change the status of many applications at once
+    ///
+    /// The applications can either be selected by their IDs or by a filter, but not both. The
+    /// same transitions as `changeApplicationStatus` are allowed. All valid transitions are
+    /// applied together, while any invalid transitions are reported as errors.
+    #[instrument(name = "Mutation::bulk_change_application_status", skip(self, ctx))]
+    async fn bulk_change_application_status(
+        &self,
+        ctx: &Context<'_>,
+        input: BulkChangeApplicationStatusInput,
+    ) -> Result<BulkChangeApplicationStatusResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let tokens = ctx.data_unchecked::<CheckInTokens>();
+        let mut txn = db.begin().await?;
+
+        let mut user_errors = Vec::new();
+        let applications = match (&input.ids, &input.filter) {
+            (Some(ids), None) => {
+                let applications = Application::find_many(&scope.event, ids, &mut txn)
+                    .await
+                    .extend()?;
+
+                for id in ids {
+                    if !applications.iter().any(|a| a.participant_id == *id) {
+                        user_errors.push(UserError::new(
+                            &["input", "ids"],
+                            format!("application {id} not found"),
+                        ));
+                    }
+                }
+
+                applications
             }
-        });
+            (None, Some(filter)) => Application::list(
+                &scope.event,
+                filter,
+                ApplicationOrder::default(),
+                None,
+                None,
+                &mut txn,
+            )
+            .await
+            .extend()?,
+            _ => {
+                return Ok(BulkChangeApplicationStatusResult {
+                    applications: Vec::with_capacity(0),
+                    user_errors: vec![UserError::new(
+                        &["input"],
+                        "exactly one of ids or filter must be provided",
+                    )],
+                })
+            }
+        };
+
+        let mut ids = Vec::with_capacity(applications.len());
+        let mut previous_statuses = HashMap::with_capacity(applications.len());
+        for application in &applications {
+            if is_valid_transition(application.status, input.status) {
+                ids.push(application.participant_id);
+                previous_statuses.insert(application.participant_id, application.status);
+            } else {
+                user_errors.push(UserError::new(
+                    &["input", "ids"],
+                    format!(
+                        "invalid status transition for application {}",
+                        application.participant_id
+                    ),
+                ));
+            }
+        }
+
+        let applications = Application::set_status_many(&scope.event, &ids, input.status, &mut txn)
+            .await
+            .extend()?;
+        let emails = Email::find_many(&ids, &mut txn).await.extend()?;
+        for application in &applications {
+            let Some(email) = emails
+                .iter()
+                .find(|email| email.participant_id == application.participant_id)
+            else {
+                continue;
+            };
+
+            notify::application_status(&mut txn, tokens, application, &email.address)
+                .await
+                .extend()?;
+        }
+        for application in &applications {
+            webhooks::enqueue(
+                &mut txn,
+                "application.status_changed",
+                &scope.event,
+                &StatusChanged {
+                    previous_status: previous_statuses[&application.participant_id],
+                    application,
+                },
+            )
+            .await
+            .extend()?;
+        }
 
         txn.commit().await?;
 
-        Ok(application.into())
+        Ok(BulkChangeApplicationStatusResult {
+            applications,
+            user_errors,
+        })
     }
+
+    /// Accept applications from the waitlist, in the order they were waitlisted
+    ///
+    /// By default, enough applications are accepted to fill the event's remaining capacity. If a
+    /// count is provided, at most that many applications are accepted, without exceeding the
+    /// event's capacity.
+    #[instrument(name = "Mutation::promote_from_waitlist", skip(self, ctx))]
+    async fn promote_from_waitlist(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(validator(minimum = 1))] count: Option<i32>,
+    ) -> Result<PromoteFromWaitlistResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let tokens = ctx.data_unchecked::<CheckInTokens>();
+        if count.is_none() {
+            let settings = EventSettings::find(&scope.event, db).await.extend()?;
+            if settings.capacity.is_none() {
+                return Ok(UserError::new(
+                    &["count"],
+                    "the event has no capacity, a count must be provided",
+                )
+                .into());
+            }
+        }
+
+        let applications = waitlist::promote(db, tokens, &scope.event, count.map(i64::from))
+            .await
+            .extend()?;
+
+        Ok(applications.into())
+    }
+}
+
+/// Check whether an application can move from one status to another
+fn is_valid_transition(from: ApplicationStatus, to: ApplicationStatus) -> bool {
+    !matches!(
+        (from, to),
+        (_, ApplicationStatus::Pending)
+            | (ApplicationStatus::Waitlisted, ApplicationStatus::Waitlisted)
+            | (_, ApplicationStatus::Confirmed)
+            | (_, ApplicationStatus::Declined)
+            | (_, ApplicationStatus::Withdrawn)
+            | (ApplicationStatus::Accepted, _)
+            | (ApplicationStatus::Rejected, _)
+            | (ApplicationStatus::Confirmed, _)
+            | (ApplicationStatus::Declined, _)
+            | (ApplicationStatus::Withdrawn, _)
+    )
+}
+
+/// Find the fields that differ between two versions of an application
+///
+/// Each changed field is mapped to its previous and new values.
+fn changed_fields(previous: &Application, current: &Application) -> Map<String, Value> {
+    let (Ok(Value::Object(previous)), Ok(Value::Object(current))) = (
+        serde_json::to_value(previous),
+        serde_json::to_value(current),
+    ) else {
+        return Map::new();
+    };
+
+    current
+        .into_iter()
+        .filter_map(|(field, value)| {
+            let before = previous.get(&field).cloned().unwrap_or(Value::Null);
+            (before != value).then(|| (field, json!({ "from": before, "to": value })))
+        })
+        .collect()
+}
+
+/// The result of changing the status of many applications
+#[derive(Debug, SimpleObject)]
+struct BulkChangeApplicationStatusResult {
+    /// The applications that were updated
+    applications: Vec<Application>,
+    /// Errors for the applications that could not be updated
+    user_errors: Vec<UserError>,
 }
 
 /// Input fields for updating an application
@@ -202,6 +789,57 @@ struct UpdateApplicationInput {
     notes: Option<String>,
 }
 
+/// Input fields for changing a submitted application
+///
+/// Fields that are required in an application can be changed, but not cleared.
+#[derive(Debug, InputObject)]
+struct UpdateSubmittedApplicationInput {
+    /// The participant's gender
+    gender: Option<Gender>,
+    /// The participant's race/ethnicity
+    race_ethnicity: Option<RaceEthnicity>,
+    /// Participant birthday
+    date_of_birth: Option<NaiveDate>,
+    /// How the participant found the event
+    referrer: MaybeUndefined<Referrer>,
+
+    /// The ID of the school that the participant attends
+    school_id: MaybeUndefined<Uuid>,
+    /// The highest level of education the participant has achieved/is working on
+    education: Option<Education>,
+    /// When the participant will graduate/graduated
+    graduation_year: Option<i32>,
+    /// What the participant is studying
+    major: MaybeUndefined<String>,
+
+    /// How many hackathons the participant has attended
+    hackathons_attended: Option<i32>,
+    /// The public VCS URL (i.e. GitHub, GitLab, BitBucket, etc.)
+    vcs_url: MaybeUndefined<String>,
+    /// The URL to the participant's portfolio
+    portfolio_url: MaybeUndefined<String>,
+    /// The URL to the participant's DevPost profile
+    devpost_url: MaybeUndefined<String>,
+
+    /// The first line of the shipping address
+    address_line1: Option<String>,
+    /// The second line of the shipping address
+    address_line2: MaybeUndefined<String>,
+    /// The last line of the shipping address
+    address_line3: MaybeUndefined<String>,
+    /// The city/town of the shipping address
+    locality: MaybeUndefined<String>,
+    /// The state/province/region of the shipping address
+    administrative_area: MaybeUndefined<String>,
+    /// The postal code of the shipping address
+    postal_code: Option<String>,
+    /// The ISO code of the country the shipping address is located in
+    country: Option<String>,
+
+    /// Whether the participant wishes to share information with sponsors
+    share_information: Option<bool>,
+}
+
 /// Input fields for changing an application's status
 #[derive(Debug, InputObject)]
 struct ChangeApplicationStatusInput {
@@ -210,3 +848,14 @@ struct ChangeApplicationStatusInput {
     /// The new status for the application
     status: ApplicationStatus,
 }
+
+/// Input fields for changing the status of many applications
+#[derive(Debug, InputObject)]
+struct BulkChangeApplicationStatusInput {
+    /// The IDs of the applications/participants to change
+    ids: Option<Vec<i32>>,
+    /// The conditions the applications to change must match
+    filter: Option<ApplicationFilter>,
+    /// The new status for the applications
+    status: ApplicationStatus,
+}
@@ -0,0 +1,232 @@
+use super::{results, UserError};
+use async_graphql::{Context, InputObject, MaybeUndefined, Object, Result, ResultExt};
+use context::{checks, UserRole};
+use database::{ApplicationQuestion, ApplicationQuestionKind, PgPool};
+use std::collections::HashSet;
+use tracing::instrument;
+use uuid::Uuid;
+
+results! {
+    CreateApplicationQuestionResult {
+        /// The created question
+        question: ApplicationQuestion,
+    }
+    UpdateApplicationQuestionResult {
+        /// The updated question
+        question: ApplicationQuestion,
+    }
+    DeleteApplicationQuestionResult {
+        /// The ID of the deleted question
+        deleted_id: Uuid,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "ApplicationQuestionMutation")]
+impl Mutation {
+    /// Add a question to the event's application
+    #[instrument(name = "Mutation::create_application_question", skip(self, ctx))]
+    async fn create_application_question(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateApplicationQuestionInput,
+    ) -> Result<CreateApplicationQuestionResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let options = input.options.unwrap_or_default();
+        let errors = check_settings(input.kind, &options, input.minimum, input.maximum);
+        if !errors.is_empty() {
+            return Ok(errors.into());
+        }
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let question = ApplicationQuestion::create(
+            &scope.event,
+            &input.label,
+            input.description.as_deref().unwrap_or_default(),
+            input.kind,
+            input.required.unwrap_or_default(),
+            input.position.unwrap_or_default(),
+            &options,
+            input.minimum,
+            input.maximum,
+            db,
+        )
+        .await
+        .extend()?;
+
+        Ok(question.into())
+    }
+
+    /// Update a question in the event's application
+    ///
+    /// The kind of question cannot be changed since it would invalidate any existing answers.
+    #[instrument(name = "Mutation::update_application_question", skip(self, ctx))]
+    async fn update_application_question(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateApplicationQuestionInput,
+    ) -> Result<UpdateApplicationQuestionResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let Some(mut question) = ApplicationQuestion::find(&scope.event, input.id, db)
+            .await
+            .extend()?
+        else {
+            return Ok(UserError::new(&["input", "id"], "question not found").into());
+        };
+
+        let minimum = Option::<Option<i32>>::from(input.minimum);
+        let maximum = Option::<Option<i32>>::from(input.maximum);
+        let errors = check_settings(
+            question.kind,
+            input.options.as_deref().unwrap_or(&question.options),
+            minimum.unwrap_or(question.minimum),
+            maximum.unwrap_or(question.maximum),
+        );
+        if !errors.is_empty() {
+            return Ok(errors.into());
+        }
+
+        question
+            .update()
+            .override_label(input.label)
+            .override_description(input.description)
+            .override_required(input.required)
+            .override_position(input.position)
+            .override_options(input.options)
+            .override_minimum(minimum)
+            .override_maximum(maximum)
+            .save(db)
+            .await
+            .extend()?;
+
+        Ok(question.into())
+    }
+
+    /// Remove a question from the event's application
+    ///
+    /// Any answers given for the question are also removed.
+    #[instrument(name = "Mutation::delete_application_question", skip(self, ctx))]
+    async fn delete_application_question(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> Result<DeleteApplicationQuestionResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        if !ApplicationQuestion::delete(&scope.event, id, db)
+            .await
+            .extend()?
+        {
+            return Ok(UserError::new(&["id"], "question not found").into());
+        }
+
+        Ok(id.into())
+    }
+}
+
+/// Check that the settings make sense for the kind of question
+fn check_settings(
+    kind: ApplicationQuestionKind,
+    options: &[String],
+    minimum: Option<i32>,
+    maximum: Option<i32>,
+) -> Vec<UserError> {
+    let mut errors = Vec::new();
+
+    let is_choice = matches!(
+        kind,
+        ApplicationQuestionKind::SingleChoice | ApplicationQuestionKind::MultipleChoice
+    );
+    if is_choice && options.is_empty() {
+        errors.push(UserError::new(
+            &["input", "options"],
+            "must have at least one option",
+        ));
+    } else if !is_choice && !options.is_empty() {
+        errors.push(UserError::new(
+            &["input", "options"],
+            "can only be set for choice questions",
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    if !options.iter().all(|option| seen.insert(option)) {
+        errors.push(UserError::new(&["input", "options"], "must be unique"));
+    }
+
+    let is_bounded = !matches!(
+        kind,
+        ApplicationQuestionKind::SingleChoice | ApplicationQuestionKind::Boolean
+    );
+    if !is_bounded && (minimum.is_some() || maximum.is_some()) {
+        errors.push(UserError::new(
+            &["input"],
+            "minimum and maximum cannot be set for this kind of question",
+        ));
+    }
+
+    if let (Some(minimum), Some(maximum)) = (minimum, maximum) {
+        if minimum > maximum {
+            errors.push(UserError::new(
+                &["input", "minimum"],
+                "cannot be greater than the maximum",
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Input fields for creating an application question
+#[derive(Debug, InputObject)]
+struct CreateApplicationQuestionInput {
+    /// The question being asked
+    label: String,
+    /// Additional details about the question
+    description: Option<String>,
+    /// The type of answer the question accepts
+    kind: ApplicationQuestionKind,
+    /// Whether the question must be answered, defaults to false
+    required: Option<bool>,
+    /// Where the question appears in the application, in ascending order
+    position: Option<i32>,
+
+    /// The options that can be chosen, required for choice questions
+    options: Option<Vec<String>>,
+    /// The lower bound of an answer, inclusive
+    minimum: Option<i32>,
+    /// The upper bound of an answer, inclusive
+    maximum: Option<i32>,
+}
+
+/// Input fields for updating an application question
+#[derive(Debug, InputObject)]
+struct UpdateApplicationQuestionInput {
+    /// The ID of the question
+    id: Uuid,
+
+    /// The question being asked
+    label: Option<String>,
+    /// Additional details about the question
+    description: Option<String>,
+    /// Whether the question must be answered
+    required: Option<bool>,
+    /// Where the question appears in the application, in ascending order
+    position: Option<i32>,
+
+    /// The options that can be chosen
+    options: Option<Vec<String>>,
+    /// The lower bound of an answer, inclusive
+    minimum: MaybeUndefined<i32>,
+    /// The upper bound of an answer, inclusive
+    maximum: MaybeUndefined<i32>,
+}
@@ -1,8 +1,12 @@
 use super::{results, UserError};
+use crate::{check_in_token::CheckInTokens, webhooks};
 use async_graphql::{Context, Object, Result, ResultExt};
 use chrono::{DateTime, Utc};
 use context::{checks, UserRole};
-use database::{Application, CheckIn, PgPool};
+use database::{
+    Application, CheckIn, CheckpointScan, EventSettings, KioskSyncEntry, PgConnection, PgPool,
+    PresenceDirection, PresenceEntry,
+};
 use tracing::instrument;
 
 results! {
@@ -10,6 +14,14 @@ results! {
         /// WHen the participant checked in
         at: DateTime<Utc>,
     }
+    CheckOutResult {
+        /// The participant's updated check in
+        check_in: CheckIn,
+    }
+    UndoCheckInResult {
+        /// The ID of the participant whose check in was removed
+        participant_id: i32,
+    }
 }
 
 #[derive(Default)]
@@ -18,6 +30,8 @@ pub(crate) struct Mutation;
 #[Object(name = "CheckInMutation")]
 impl Mutation {
     /// Check in a participant to the event
+    ///
+    /// If the event requires it, participants must have confirmed their attendance first.
     #[instrument(name = "Mutation::check_in", skip_all)]
     async fn check_in(&self, ctx: &Context<'_>, id: Option<i32>) -> Result<CheckInResult> {
         let scope = checks::is_event(ctx)?;
@@ -36,24 +50,194 @@ impl Mutation {
             user.id
         };
 
+        let db = ctx.data_unchecked::<PgPool>();
+        check_in_participant(&["id"], &scope.event, id, user.id, db).await
+    }
+
+    /// Check in a participant to the event using the token from their QR code
+    ///
+    /// The same requirements as `checkIn` apply.
+    #[instrument(name = "Mutation::check_in_with_token", skip_all)]
+    async fn check_in_with_token(&self, ctx: &Context<'_>, token: String) -> Result<CheckInResult> {
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let tokens = ctx.data_unchecked::<CheckInTokens>();
+        let Some(id) = tokens.verify(&scope.event, &token) else {
+            return Ok(UserError::new(&["token"], "invalid check-in token").into());
+        };
+
+        let db = ctx.data_unchecked::<PgPool>();
+        check_in_participant(&["token"], &scope.event, id, user.id, db).await
+    }
+
+    /// Check out a participant from the event
+    ///
+    /// Participants that are checked out can be checked in again later.
+    #[instrument(name = "Mutation::check_out", skip_all)]
+    async fn check_out(&self, ctx: &Context<'_>, id: Option<i32>) -> Result<CheckOutResult> {
+        let scope = checks::is_event(ctx)?;
+        let user = checks::is_authenticated(ctx)?;
+
+        let id = if let Some(id) = id {
+            if user.id == id {
+                checks::has_role(ctx, UserRole::Participant)?;
+            } else {
+                checks::has_at_least_role(ctx, UserRole::Organizer)?;
+            }
+
+            id
+        } else {
+            checks::has_role(ctx, UserRole::Participant)?;
+            user.id
+        };
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let mut txn = db.begin().await?;
+
+        let Some(check_in) = CheckIn::check_out(&scope.event, id, &mut *txn)
+            .await
+            .extend()?
+        else {
+            return Ok(UserError::new(&["id"], "participant is not checked in").into());
+        };
+
+        PresenceEntry::record(
+            &scope.event,
+            id,
+            PresenceDirection::Out,
+            user.id,
+            Utc::now(),
+            &mut *txn,
+        )
+        .await
+        .extend()?;
+        webhooks::enqueue(&mut txn, "participant.checked_out", &scope.event, &check_in)
+            .await
+            .extend()?;
+
+        txn.commit().await?;
+
+        Ok(check_in.into())
+    }
+
+    /// Remove a participant's check in, along with their history of entering and leaving, their
+    /// checkpoint scans, and any entries synced from kiosks for them
+    ///
+    /// Meant for correcting participants that were checked in by mistake.
+    #[instrument(name = "Mutation::undo_check_in", skip(self, ctx))]
+    async fn undo_check_in(&self, ctx: &Context<'_>, id: i32) -> Result<UndoCheckInResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
         let db = ctx.data_unchecked::<PgPool>();
         let mut txn = db.begin().await?;
 
-        if !Application::accepted_exists(&scope.event, id, &mut *txn)
+        if !CheckIn::delete(&scope.event, id, &mut *txn)
             .await
             .extend()?
         {
-            return Ok(UserError::new(
-                &["id"],
-                "only participants with accepted applications can check in",
-            )
-            .into());
+            return Ok(UserError::new(&["id"], "participant is not checked in").into());
         }
-
-        let check_in = CheckIn::mark(&scope.event, id, &mut *txn).await.extend()?;
+        PresenceEntry::delete_for_participant(&scope.event, id, &mut *txn)
+            .await
+            .extend()?;
+        CheckpointScan::delete_for_participant(&scope.event, id, &mut *txn)
+            .await
+            .extend()?;
+        KioskSyncEntry::delete_for_participant(&scope.event, id, &mut *txn)
+            .await
+            .extend()?;
 
         txn.commit().await?;
 
-        Ok(check_in.at.into())
+        Ok(id.into())
     }
 }
+
+/// Check in a participant, ensuring they are allowed to attend the event
+///
+/// Checking in a participant who is already present does nothing.
+async fn check_in_participant(
+    field: &'static [&'static str],
+    event: &str,
+    id: i32,
+    actor_id: i32,
+    db: &PgPool,
+) -> Result<CheckInResult> {
+    let mut txn = db.begin().await?;
+
+    let settings = EventSettings::find(event, &mut *txn).await.extend()?;
+    if let Some(reason) = check_in_rejection(&mut txn, event, id, settings.check_in_requires_rsvp)
+        .await
+        .extend()?
+    {
+        return Ok(UserError::new(field, reason).into());
+    }
+
+    let check_in = mark_checked_in(&mut txn, event, id, actor_id, Utc::now())
+        .await
+        .extend()?;
+
+    txn.commit().await?;
+
+    Ok(check_in.at.into())
+}
+
+/// Get the reason a participant cannot check in to the event, if they are not allowed to
+pub(super) async fn check_in_rejection(
+    conn: &mut PgConnection,
+    event: &str,
+    id: i32,
+    requires_rsvp: bool,
+) -> Result<Option<&'static str>, database::Error> {
+    if requires_rsvp {
+        if !Application::confirmed_exists(event, id, &mut *conn).await? {
+            return Ok(Some(
+                "only participants who confirmed their attendance can check in",
+            ));
+        }
+    } else if !Application::accepted_exists(event, id, &mut *conn).await? {
+        return Ok(Some(
+            "only participants with accepted applications can check in",
+        ));
+    }
+
+    Ok(None)
+}
+
+/// Mark a participant as checked in at the given time
+///
+/// The arrival is only recorded, and the webhook only published, when the participant was not
+/// already present and is present afterwards. Arrivals synced from kiosks can predate the
+/// participant's latest check out, in which case they stay checked out and nothing is recorded.
+/// The participant's check in is locked first so that concurrent check ins cannot both record
+/// the arrival.
+pub(super) async fn mark_checked_in(
+    conn: &mut PgConnection,
+    event: &str,
+    id: i32,
+    actor_id: i32,
+    at: DateTime<Utc>,
+) -> Result<CheckIn, database::Error> {
+    let (check_in, arrived) = match CheckIn::find_for_update(event, id, &mut *conn).await? {
+        Some(previous) => {
+            let check_in = CheckIn::mark(event, id, at, &mut *conn).await?;
+            let arrived = previous.checked_out_at.is_some() && check_in.checked_out_at.is_none();
+            (check_in, arrived)
+        }
+        None => match CheckIn::create(event, id, at, &mut *conn).await? {
+            Some(check_in) => (check_in, true),
+            // someone else checked in the participant after the lookup
+            None => (CheckIn::mark(event, id, at, &mut *conn).await?, false),
+        },
+    };
+
+    if arrived {
+        PresenceEntry::record(event, id, PresenceDirection::In, actor_id, at, &mut *conn).await?;
+        webhooks::enqueue(conn, "participant.checked_in", event, &check_in).await?;
+    }
+
+    Ok(check_in)
+}
@@ -0,0 +1,278 @@
+use super::{results, UserError};
+use crate::check_in_token::CheckInTokens;
+use async_graphql::{Context, InputObject, MaybeUndefined, Object, Result, ResultExt};
+use chrono::Utc;
+use context::{checks, UserRole};
+use database::{CheckIn, Checkpoint, CheckpointScan, PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+results! {
+    CreateCheckpointResult {
+        /// The created checkpoint
+        checkpoint: Checkpoint,
+    }
+    UpdateCheckpointResult {
+        /// The updated checkpoint
+        checkpoint: Checkpoint,
+    }
+    DeleteCheckpointResult {
+        /// The ID of the deleted checkpoint
+        deleted_id: Uuid,
+    }
+    ScanAtCheckpointResult {
+        /// The recorded scan
+        scan: CheckpointScan,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "CheckpointMutation")]
+impl Mutation {
+    /// Add a checkpoint to the event
+    #[instrument(name = "Mutation::create_checkpoint", skip(self, ctx))]
+    async fn create_checkpoint(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateCheckpointInput,
+    ) -> Result<CreateCheckpointResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        if !is_valid_slug(&input.slug) {
+            return Ok(UserError::new(&["input", "slug"], INVALID_SLUG).into());
+        }
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let result = Checkpoint::create(
+            &scope.event,
+            &input.slug,
+            &input.name,
+            input.capacity,
+            input.once_only.unwrap_or_default(),
+            db,
+        )
+        .await;
+
+        match result {
+            Ok(checkpoint) => Ok(checkpoint.into()),
+            Err(error) if error.is_unique_violation() => {
+                Ok(UserError::new(&["input", "slug"], "already in use").into())
+            }
+            Err(error) => Err(error).extend(),
+        }
+    }
+
+    /// Update a checkpoint in the event
+    #[instrument(name = "Mutation::update_checkpoint", skip(self, ctx))]
+    async fn update_checkpoint(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateCheckpointInput,
+    ) -> Result<UpdateCheckpointResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        if input
+            .slug
+            .as_deref()
+            .is_some_and(|slug| !is_valid_slug(slug))
+        {
+            return Ok(UserError::new(&["input", "slug"], INVALID_SLUG).into());
+        }
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let Some(mut checkpoint) = Checkpoint::find(&scope.event, input.id, db)
+            .await
+            .extend()?
+        else {
+            return Ok(UserError::new(&["input", "id"], "checkpoint not found").into());
+        };
+
+        let result = checkpoint
+            .update()
+            .override_slug(input.slug)
+            .override_name(input.name)
+            .override_capacity(input.capacity.into())
+            .override_once_only(input.once_only)
+            .save(db)
+            .await;
+
+        match result {
+            Ok(()) => Ok(checkpoint.into()),
+            Err(error) if error.is_unique_violation() => {
+                Ok(UserError::new(&["input", "slug"], "already in use").into())
+            }
+            Err(error) => Err(error).extend(),
+        }
+    }
+
+    /// Remove a checkpoint from the event
+    ///
+    /// Any scans at the checkpoint are also removed.
+    #[instrument(name = "Mutation::delete_checkpoint", skip(self, ctx))]
+    async fn delete_checkpoint(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> Result<DeleteCheckpointResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        if !Checkpoint::delete(&scope.event, id, db).await.extend()? {
+            return Ok(UserError::new(&["id"], "checkpoint not found").into());
+        }
+
+        Ok(id.into())
+    }
+
+    /// Record a participant being scanned at a checkpoint
+    ///
+    /// The participant can be identified either by their ID or the token from their QR code, but
+    /// not both. Only participants who checked in to the event can be scanned.
+    #[instrument(name = "Mutation::scan_at_checkpoint", skip(self, ctx))]
+    async fn scan_at_checkpoint(
+        &self,
+        ctx: &Context<'_>,
+        input: ScanAtCheckpointInput,
+    ) -> Result<ScanAtCheckpointResult> {
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let (id, field): (i32, &[&str]) = match (input.id, input.token.as_deref()) {
+            (Some(id), None) => (id, &["input", "id"]),
+            (None, Some(token)) => {
+                let tokens = ctx.data_unchecked::<CheckInTokens>();
+                let Some(id) = tokens.verify(&scope.event, token) else {
+                    return Ok(
+                        UserError::new(&["input", "token"], "invalid check-in token").into(),
+                    );
+                };
+
+                (id, &["input", "token"])
+            }
+            _ => {
+                return Ok(UserError::new(
+                    &["input"],
+                    "exactly one of id or token must be provided",
+                )
+                .into())
+            }
+        };
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let mut txn = db.begin().await?;
+
+        let Some(checkpoint) =
+            Checkpoint::find_for_update(&scope.event, input.checkpoint_id, &mut txn)
+                .await
+                .extend()?
+        else {
+            return Ok(UserError::new(&["input", "checkpointId"], "checkpoint not found").into());
+        };
+
+        if let Some(reason) = scan_rejection(&mut txn, &checkpoint, id).await.extend()? {
+            return Ok(UserError::new(field, reason).into());
+        }
+
+        let scan = CheckpointScan::record(
+            &scope.event,
+            checkpoint.id,
+            id,
+            user.id,
+            Utc::now(),
+            &mut txn,
+        )
+        .await
+        .extend()?;
+
+        txn.commit().await?;
+
+        Ok(scan.into())
+    }
+}
+
+/// Get the reason a participant cannot be scanned at a checkpoint, if they are not allowed to be
+///
+/// Participants must have checked in to the event, and the checkpoint's rules must allow it.
+pub(super) async fn scan_rejection(
+    conn: &mut PgConnection,
+    checkpoint: &Checkpoint,
+    id: i32,
+) -> Result<Option<&'static str>, database::Error> {
+    if !CheckIn::exists(&checkpoint.event, id, &mut *conn).await? {
+        return Ok(Some("participant has not checked in to the event"));
+    }
+
+    let scanned = CheckpointScan::exists(checkpoint.id, id, &mut *conn).await?;
+    if scanned && checkpoint.once_only {
+        return Ok(Some("participant was already scanned"));
+    }
+
+    if let Some(capacity) = checkpoint.capacity {
+        let participants = CheckpointScan::count_participants(checkpoint.id, &mut *conn).await?;
+        if !scanned && participants >= i64::from(capacity) {
+            return Ok(Some("checkpoint is at capacity"));
+        }
+    }
+
+    Ok(None)
+}
+
+/// The error reported for malformed slugs
+const INVALID_SLUG: &str = "must only contain lowercase letters, numbers, and dashes";
+
+/// Check that a slug is non-empty and only contains lowercase letters, numbers, and dashes
+fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Input fields for creating a checkpoint
+#[derive(Debug, InputObject)]
+struct CreateCheckpointInput {
+    /// A unique, URL-safe identifier for the checkpoint within the event
+    slug: String,
+    /// A human-readable name for the checkpoint
+    name: String,
+    /// The maximum number of different participants that can be scanned, if limited
+    #[graphql(validator(minimum = 1))]
+    capacity: Option<i32>,
+    /// Whether participants can only be scanned once, defaults to false
+    once_only: Option<bool>,
+}
+
+/// Input fields for updating a checkpoint
+#[derive(Debug, InputObject)]
+struct UpdateCheckpointInput {
+    /// The ID of the checkpoint
+    id: Uuid,
+
+    /// A unique, URL-safe identifier for the checkpoint within the event
+    slug: Option<String>,
+    /// A human-readable name for the checkpoint
+    name: Option<String>,
+    /// The maximum number of different participants that can be scanned, if limited
+    #[graphql(validator(minimum = 1))]
+    capacity: MaybeUndefined<i32>,
+    /// Whether participants can only be scanned once
+    once_only: Option<bool>,
+}
+
+/// Input fields for scanning a participant at a checkpoint
+#[derive(Debug, InputObject)]
+struct ScanAtCheckpointInput {
+    /// The ID of the checkpoint
+    checkpoint_id: Uuid,
+
+    /// The ID of the participant
+    id: Option<i32>,
+    /// The token from the participant's QR code
+    token: Option<String>,
+}
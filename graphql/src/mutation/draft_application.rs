@@ -1,10 +1,11 @@
-use super::{results, UserError};
-use crate::errors::Forbidden;
+use super::{results, set_option, UserError};
+use crate::{errors::Forbidden, webhooks};
 use async_graphql::{Context, InputObject, MaybeUndefined, Object, Result, ResultExt};
 use chrono::NaiveDate;
 use context::{checks, UserRole};
 use database::{
-    Application, DraftApplication, Education, Gender, PgPool, RaceEthnicity, Referrer, School,
+    Answer, AnswerValue, Application, ApplicationQuestion, DraftApplication, Education, Gender,
+    PgPool, RaceEthnicity, Referrer, School,
 };
 use tracing::instrument;
 use uuid::Uuid;
@@ -16,16 +17,6 @@ results! {
     }
 }
 
-macro_rules! set_option {
-    ($source:expr => $destination:expr) => {
-        match $source {
-            MaybeUndefined::Value(value) => $destination = Some(value),
-            MaybeUndefined::Null => $destination = None,
-            MaybeUndefined::Undefined => {}
-        }
-    };
-}
-
 #[derive(Default)]
 pub(crate) struct Mutation;
 
@@ -57,10 +48,12 @@ impl Mutation {
             );
         }
 
-        let mut draft = DraftApplication::find(&scope.event, user.id, &mut txn)
+        let existing = DraftApplication::find(&scope.event, user.id, &mut txn)
             .await
-            .extend()?
-            .unwrap_or_else(|| DraftApplication::new(scope.event.clone(), user.id));
+            .extend()?;
+        let started = existing.is_none();
+        let mut draft =
+            existing.unwrap_or_else(|| DraftApplication::new(scope.event.clone(), user.id));
 
         match input.school_id {
             MaybeUndefined::Value(school_id) => {
@@ -96,7 +89,65 @@ impl Mutation {
             draft.share_information = share_information;
         }
 
+        let questions = ApplicationQuestion::all(&scope.event, &mut txn)
+            .await
+            .extend()?;
+        let mut answers = Vec::new();
+        let mut cleared = Vec::new();
+        for answer in input.answers.unwrap_or_default() {
+            if !questions
+                .iter()
+                .any(|question| question.id == answer.question_id)
+            {
+                return Ok(UserError::item(
+                    &["input", "answers"],
+                    answer.question_id,
+                    "unknown question",
+                )
+                .into());
+            }
+
+            match answer.value {
+                Some(value) => answers.push(Answer {
+                    event: scope.event.clone(),
+                    participant_id: user.id,
+                    question_id: answer.question_id,
+                    value,
+                }),
+                None => cleared.push(answer.question_id),
+            }
+        }
+
+        let mut errors = draft.validate();
+        errors.extend(ApplicationQuestion::validate_answers(
+            &questions, &answers, false,
+        ));
+        if !errors.is_empty() {
+            return Ok(UserError::from_validation(errors).into());
+        }
+
         draft.save(&mut txn).await.extend()?;
+        for answer in &answers {
+            Answer::save_draft(
+                &scope.event,
+                user.id,
+                answer.question_id,
+                &answer.value,
+                &mut txn,
+            )
+            .await
+            .extend()?;
+        }
+        for question_id in cleared {
+            Answer::delete_draft(&scope.event, user.id, question_id, &mut txn)
+                .await
+                .extend()?;
+        }
+        if started {
+            webhooks::enqueue(&mut txn, "draft_application.started", &scope.event, &draft)
+                .await
+                .extend()?;
+        }
 
         txn.commit().await?;
 
@@ -151,4 +202,18 @@ struct SaveApplicationInput {
 
     /// Whether the participant wishes to share information with sponsors
     pub share_information: Option<bool>,
+
+    /// Answers to the event's application questions
+    ///
+    /// Only the questions included are changed, an answer without a value is cleared.
+    pub answers: Option<Vec<AnswerInput>>,
+}
+
+/// An answer to one of the event's application questions
+#[derive(Debug, InputObject)]
+struct AnswerInput {
+    /// The ID of the question being answered
+    pub question_id: Uuid,
+    /// The answer, or null to clear it
+    pub value: Option<AnswerValue>,
 }
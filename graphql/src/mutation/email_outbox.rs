@@ -0,0 +1,33 @@
+use super::{results, UserError};
+use async_graphql::{Context, Object, Result, ResultExt};
+use context::{checks, UserRole};
+use database::{OutboxEmail, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+results! {
+    ResendEmailResult {
+        /// The email that was queued for delivery
+        email: OutboxEmail,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "EmailOutboxMutation")]
+impl Mutation {
+    /// Queue an email that could not be delivered to be sent again
+    #[instrument(name = "Mutation::resend_email", skip(self, ctx))]
+    async fn resend_email(&self, ctx: &Context<'_>, id: Uuid) -> Result<ResendEmailResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let Some(email) = OutboxEmail::retry(&scope.event, id, db).await.extend()? else {
+            return Ok(UserError::new(&["id"], "failed email not found").into());
+        };
+
+        Ok(email.into())
+    }
+}
@@ -0,0 +1,153 @@
+use super::{results, UserError};
+use async_graphql::{Context, InputObject, MaybeUndefined, Object, Result, ResultExt};
+use context::{checks, UserRole};
+use database::{EmailSettings, PgPool};
+use mail::Template;
+use std::collections::{HashMap, HashSet};
+use tracing::instrument;
+
+results! {
+    UpdateEmailSettingsResult {
+        /// The updated settings
+        settings: EmailSettings,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "EmailSettingsMutation")]
+impl Mutation {
+    /// Update how emails for the event are sent
+    ///
+    /// Any settings that are cleared fall back to the deployment's defaults. When template
+    /// aliases are provided, they replace all the existing aliases.
+    #[instrument(name = "Mutation::update_email_settings", skip(self, ctx))]
+    async fn update_email_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateEmailSettingsInput,
+    ) -> Result<UpdateEmailSettingsResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let mut errors = Vec::new();
+        if let MaybeUndefined::Value(from_address) = &input.from_address {
+            if !is_address(from_address) {
+                errors.push(UserError::new(
+                    &["input", "fromAddress"],
+                    "must be a valid email address",
+                ));
+            }
+        }
+        if let MaybeUndefined::Value(reply_to) = &input.reply_to {
+            if !is_address(reply_to) {
+                errors.push(UserError::new(
+                    &["input", "replyTo"],
+                    "must be a valid email address",
+                ));
+            }
+        }
+        if let MaybeUndefined::Value(message_stream) = &input.message_stream {
+            if message_stream.trim().is_empty() {
+                errors.push(UserError::new(
+                    &["input", "messageStream"],
+                    "cannot be empty",
+                ));
+            }
+        }
+        let mut seen = HashSet::new();
+        for alias in input.template_aliases.iter().flatten() {
+            if !seen.insert(alias.template.as_str()) {
+                errors.push(UserError::new(
+                    &["input", "templateAliases"],
+                    format!("template {:?} has more than one alias", alias.template),
+                ));
+            } else if !Template::ALIASES.contains(&alias.template.as_str()) {
+                errors.push(UserError::new(
+                    &["input", "templateAliases"],
+                    format!("unknown template {:?}", alias.template),
+                ));
+            } else if alias.alias.trim().is_empty() {
+                errors.push(UserError::new(
+                    &["input", "templateAliases"],
+                    format!("alias for {:?} cannot be empty", alias.template),
+                ));
+            }
+        }
+        if !errors.is_empty() {
+            return Ok(errors.into());
+        }
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let mut txn = db.begin().await?;
+
+        let mut settings = EmailSettings::find(&scope.event, &mut txn).await.extend()?;
+
+        match input.from_address {
+            MaybeUndefined::Value(from_address) => settings.from_address = Some(from_address),
+            MaybeUndefined::Null => settings.from_address = None,
+            MaybeUndefined::Undefined => {}
+        }
+        match input.reply_to {
+            MaybeUndefined::Value(reply_to) => settings.reply_to = Some(reply_to),
+            MaybeUndefined::Null => settings.reply_to = None,
+            MaybeUndefined::Undefined => {}
+        }
+        match input.message_stream {
+            MaybeUndefined::Value(message_stream) => settings.message_stream = Some(message_stream),
+            MaybeUndefined::Null => settings.message_stream = None,
+            MaybeUndefined::Undefined => {}
+        }
+        if let Some(aliases) = input.template_aliases {
+            settings.template_aliases.0 = aliases
+                .into_iter()
+                .map(|alias| (alias.template, alias.alias))
+                .collect::<HashMap<_, _>>();
+        }
+
+        settings.save(&mut txn).await.extend()?;
+        let settings = EmailSettings::find(&scope.event, &mut txn).await.extend()?;
+
+        txn.commit().await?;
+
+        Ok(settings.into())
+    }
+}
+
+/// Loosely check that an address is an email address, optionally with a display name
+///
+/// Postmark performs the full validation when the email is sent.
+fn is_address(address: &str) -> bool {
+    let address = match (address.find('<'), address.strip_suffix('>')) {
+        (Some(start), Some(address)) => &address[start + 1..],
+        _ => address,
+    };
+
+    match address.split_once('@') {
+        Some((local, domain)) => !local.is_empty() && domain.contains('.'),
+        None => false,
+    }
+}
+
+/// Input fields for updating the event's email settings
+#[derive(Debug, InputObject)]
+struct UpdateEmailSettingsInput {
+    /// The address emails are sent from, optionally with a display name
+    from_address: MaybeUndefined<String>,
+    /// The address replies are sent to
+    reply_to: MaybeUndefined<String>,
+    /// The Postmark message stream to send through
+    message_stream: MaybeUndefined<String>,
+    /// Overrides for the Postmark template alias used for each template
+    template_aliases: Option<Vec<TemplateAliasInput>>,
+}
+
+/// The Postmark template alias to use in place of one of the portal's templates
+#[derive(Debug, InputObject)]
+struct TemplateAliasInput {
+    /// The portal's name for the template
+    template: String,
+    /// The alias of the template in Postmark
+    alias: String,
+}
@@ -0,0 +1,84 @@
+use super::results;
+use async_graphql::{Context, InputObject, MaybeUndefined, Object, Result, ResultExt};
+use chrono::{DateTime, Utc};
+use context::{checks, UserRole};
+use database::{EventSettings, PgPool};
+use tracing::instrument;
+
+results! {
+    UpdateEventSettingsResult {
+        /// The updated settings
+        settings: EventSettings,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "EventSettingsMutation")]
+impl Mutation {
+    /// Update the settings for the event
+    #[instrument(name = "Mutation::update_event_settings", skip(self, ctx))]
+    async fn update_event_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateEventSettingsInput,
+    ) -> Result<UpdateEventSettingsResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let mut txn = db.begin().await?;
+
+        let mut settings = EventSettings::find(&scope.event, &mut txn).await.extend()?;
+
+        match input.name {
+            MaybeUndefined::Value(name) => settings.name = Some(name),
+            MaybeUndefined::Null => settings.name = None,
+            MaybeUndefined::Undefined => {}
+        }
+        match input.capacity {
+            MaybeUndefined::Value(capacity) => settings.capacity = Some(capacity),
+            MaybeUndefined::Null => settings.capacity = None,
+            MaybeUndefined::Undefined => {}
+        }
+        match input.rsvp_window_days {
+            MaybeUndefined::Value(days) => settings.rsvp_window_days = Some(days),
+            MaybeUndefined::Null => settings.rsvp_window_days = None,
+            MaybeUndefined::Undefined => {}
+        }
+        if let Some(required) = input.check_in_requires_rsvp {
+            settings.check_in_requires_rsvp = required;
+        }
+        match input.application_edit_deadline {
+            MaybeUndefined::Value(deadline) => settings.application_edit_deadline = Some(deadline),
+            MaybeUndefined::Null => settings.application_edit_deadline = None,
+            MaybeUndefined::Undefined => {}
+        }
+
+        settings.save(&mut txn).await.extend()?;
+        let settings = EventSettings::find(&scope.event, &mut txn).await.extend()?;
+
+        txn.commit().await?;
+
+        Ok(settings.into())
+    }
+}
+
+/// Input fields for updating the event's settings
+#[derive(Debug, InputObject)]
+struct UpdateEventSettingsInput {
+    /// The name of the event shown to participants, defaults to the event's slug
+    #[graphql(validator(min_length = 1))]
+    name: MaybeUndefined<String>,
+    /// The maximum number of participants that can be accepted, if limited
+    #[graphql(validator(minimum = 0))]
+    capacity: MaybeUndefined<i32>,
+    /// How many days accepted participants have to confirm their attendance, if limited
+    #[graphql(validator(minimum = 1))]
+    rsvp_window_days: MaybeUndefined<i32>,
+    /// Whether participants must confirm their attendance before they can check-in
+    check_in_requires_rsvp: Option<bool>,
+    /// When participants can no longer change their submitted applications, if limited
+    application_edit_deadline: MaybeUndefined<DateTime<Utc>>,
+}
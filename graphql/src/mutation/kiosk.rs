@@ -0,0 +1,206 @@
+use super::{
+    check_in::{check_in_rejection, mark_checked_in},
+    checkpoint::scan_rejection,
+};
+use async_graphql::{Context, InputObject, Object, Result, ResultExt, SimpleObject};
+use chrono::{DateTime, Duration, Utc};
+use context::{checks, UserRole};
+use database::{Checkpoint, CheckpointScan, EventSettings, KioskSyncEntry, PgPool};
+use std::collections::HashMap;
+use tracing::instrument;
+
+/// How far ahead of the server's clock a kiosk's clock can be
+const MAX_CLOCK_SKEW_MINUTES: i64 = 5;
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "KioskMutation")]
+impl Mutation {
+    /// Upload the check ins and checkpoint scans a kiosk recorded while offline
+    ///
+    /// Entries are applied in the order they were recorded, using the same rules as `checkIn` and
+    /// `scanAtCheckpoint`. Syncing is idempotent, entries that were already synced are skipped.
+    /// When a participant's check in conflicts with an existing one, the earliest arrival is kept.
+    #[instrument(name = "Mutation::sync_kiosk_check_ins", skip_all)]
+    async fn sync_kiosk_check_ins(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(validator(max_items = 500))] entries: Vec<KioskEntryInput>,
+    ) -> Result<SyncKioskCheckInsResult> {
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let mut entries = entries.into_iter().enumerate().collect::<Vec<_>>();
+        entries.sort_by_key(|(_, entry)| entry.client_timestamp);
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let mut txn = db.begin().await?;
+
+        let settings = EventSettings::find(&scope.event, &mut *txn)
+            .await
+            .extend()?;
+        let latest = Utc::now() + Duration::minutes(MAX_CLOCK_SKEW_MINUTES);
+
+        let mut result = SyncKioskCheckInsResult::default();
+        let mut checkpoints = HashMap::new();
+        for (index, entry) in entries {
+            let reject = |reason: &str| RejectedKioskEntry {
+                index: index as i32,
+                participant_id: entry.participant_id,
+                reason: reason.to_owned(),
+            };
+
+            if entry.client_timestamp > latest {
+                result.rejected.push(reject("recorded in the future"));
+                continue;
+            }
+
+            let checkpoint = match entry.checkpoint.as_deref() {
+                Some(slug) => {
+                    if !checkpoints.contains_key(slug) {
+                        let checkpoint = Checkpoint::find_by_slug(&scope.event, slug, &mut *txn)
+                            .await
+                            .extend()?;
+                        let checkpoint = match checkpoint {
+                            // prevents concurrent scans from exceeding the checkpoint's rules
+                            Some(checkpoint) => {
+                                Checkpoint::find_for_update(&scope.event, checkpoint.id, &mut *txn)
+                                    .await
+                                    .extend()?
+                            }
+                            None => None,
+                        };
+                        checkpoints.insert(slug.to_owned(), checkpoint);
+                    }
+
+                    match &checkpoints[slug] {
+                        Some(checkpoint) => Some(checkpoint),
+                        None => {
+                            result.rejected.push(reject("checkpoint not found"));
+                            continue;
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            let rejection = match checkpoint {
+                Some(checkpoint) => scan_rejection(&mut txn, checkpoint, entry.participant_id)
+                    .await
+                    .extend()?,
+                None => check_in_rejection(
+                    &mut txn,
+                    &scope.event,
+                    entry.participant_id,
+                    settings.check_in_requires_rsvp,
+                )
+                .await
+                .extend()?,
+            };
+
+            let slug = entry.checkpoint.as_deref().unwrap_or_default();
+            if let Some(reason) = rejection {
+                // entries that were already applied may no longer pass the checks, like once-only
+                // checkpoints, so they are reported as duplicates instead
+                let synced = KioskSyncEntry::exists(
+                    &scope.event,
+                    entry.participant_id,
+                    slug,
+                    entry.client_timestamp,
+                    &mut *txn,
+                )
+                .await
+                .extend()?;
+
+                if synced {
+                    result.duplicates += 1;
+                } else {
+                    result.rejected.push(reject(reason));
+                }
+                continue;
+            }
+
+            let claimed = KioskSyncEntry::claim(
+                &scope.event,
+                entry.participant_id,
+                slug,
+                entry.client_timestamp,
+                user.id,
+                &mut *txn,
+            )
+            .await
+            .extend()?;
+            if !claimed {
+                result.duplicates += 1;
+                continue;
+            }
+
+            match checkpoint {
+                Some(checkpoint) => {
+                    CheckpointScan::record(
+                        &scope.event,
+                        checkpoint.id,
+                        entry.participant_id,
+                        user.id,
+                        entry.client_timestamp,
+                        &mut *txn,
+                    )
+                    .await
+                    .extend()?;
+                }
+                None => {
+                    mark_checked_in(
+                        &mut txn,
+                        &scope.event,
+                        entry.participant_id,
+                        user.id,
+                        entry.client_timestamp,
+                    )
+                    .await
+                    .extend()?;
+                }
+            }
+
+            result.applied += 1;
+        }
+
+        txn.commit().await?;
+
+        Ok(result)
+    }
+}
+
+/// A check in or checkpoint scan recorded by a kiosk
+#[derive(Debug, InputObject)]
+struct KioskEntryInput {
+    /// The ID of the participant
+    participant_id: i32,
+    /// The slug of the checkpoint the participant was scanned at, or null for checking in
+    checkpoint: Option<String>,
+    /// When the kiosk recorded the entry, according to its own clock
+    client_timestamp: DateTime<Utc>,
+}
+
+/// The result of syncing a kiosk's entries
+#[derive(Debug, Default, SimpleObject)]
+struct SyncKioskCheckInsResult {
+    /// The number of entries that were applied
+    applied: i32,
+    /// The number of entries that were skipped since they were already synced
+    duplicates: i32,
+    /// The entries that could not be applied
+    rejected: Vec<RejectedKioskEntry>,
+}
+
+/// An entry from a kiosk that could not be applied
+#[derive(Debug, SimpleObject)]
+struct RejectedKioskEntry {
+    /// The position of the entry in the uploaded batch
+    index: i32,
+    /// The ID of the participant
+    participant_id: i32,
+    /// Why the entry was rejected
+    reason: String,
+}
@@ -1,8 +1,22 @@
 use async_graphql::{MergedObject, Object};
+use database::ValidationError;
 
+mod announcement;
 mod application;
+mod application_question;
 mod check_in;
+mod checkpoint;
 mod draft_application;
+mod email_outbox;
+mod email_settings;
+mod event_settings;
+mod kiosk;
+mod resume;
+mod review;
+mod rubric_criterion;
+mod webhook_endpoint;
+
+pub(crate) use resume::MAX_RESUME_SIZE;
 
 /// The various GraphQL mutations
 ///
@@ -10,16 +24,27 @@ mod draft_application;
 /// attached to this one struct.
 #[derive(Default, MergedObject)]
 pub struct Mutation(
+    announcement::Mutation,
     application::Mutation,
+    application_question::Mutation,
     check_in::Mutation,
+    checkpoint::Mutation,
     draft_application::Mutation,
+    email_outbox::Mutation,
+    email_settings::Mutation,
+    event_settings::Mutation,
+    kiosk::Mutation,
+    resume::Mutation,
+    review::Mutation,
+    rubric_criterion::Mutation,
+    webhook_endpoint::Mutation,
 );
 
 /// Represents and error in the input of a mutation
 #[derive(Debug)]
 pub struct UserError {
     /// The path to the input field that caused the error
-    field: &'static [&'static str],
+    field: Vec<String>,
     /// The error message
     message: String,
 }
@@ -31,8 +56,8 @@ pub struct UserError {
 impl UserError {
     /// The path to the input field that caused the error
     #[inline(always)]
-    async fn field(&self) -> &'static [&'static str] {
-        self.field
+    async fn field(&self) -> &[String] {
+        &self.field
     }
 
     /// The error message
@@ -45,9 +70,35 @@ impl UserError {
 impl UserError {
     /// Create a new user error
     pub fn new(field: &'static [&'static str], message: impl ToString) -> Self {
+        let field = field.iter().map(|segment| segment.to_string()).collect();
         let message = message.to_string();
         Self { field, message }
     }
+
+    /// Create a new user error for one of the items in a list field, identified by the key
+    pub fn item(
+        field: &'static [&'static str],
+        key: impl ToString,
+        message: impl ToString,
+    ) -> Self {
+        let mut error = Self::new(field, message);
+        error.field.push(key.to_string());
+        error
+    }
+
+    /// Create user errors for each of the invalid fields
+    pub fn from_validation(errors: Vec<ValidationError>) -> Vec<Self> {
+        errors.into_iter().map(Self::from).collect()
+    }
+}
+
+impl From<ValidationError> for UserError {
+    fn from(error: ValidationError) -> Self {
+        Self {
+            field: error.field,
+            message: error.message,
+        }
+    }
 }
 
 /// Create mutation results with user errors
@@ -102,3 +153,16 @@ macro_rules! results {
 }
 
 pub(crate) use results;
+
+/// Apply an optional input field to a nullable value, leaving it unchanged when undefined
+macro_rules! set_option {
+    ($source:expr => $destination:expr) => {
+        match $source {
+            MaybeUndefined::Value(value) => $destination = Some(value),
+            MaybeUndefined::Null => $destination = None,
+            MaybeUndefined::Undefined => {}
+        }
+    };
+}
+
+pub(crate) use set_option;
@@ -0,0 +1,197 @@
+use super::{results, UserError};
+use crate::{errors::StorageFailed, resume_book::ResumeBookLinks, storage::Storage};
+use async_graphql::{Context, Error, Object, Result, ResultExt, SimpleObject, Upload};
+use chrono::{DateTime, Utc};
+use context::{checks, UserRole};
+use database::{Application, DraftApplication, PgPool, Resume};
+use std::io::Read;
+use tracing::{instrument, warn};
+use uuid::Uuid;
+
+/// The largest resume that can be uploaded (5 MiB)
+pub(crate) const MAX_RESUME_SIZE: u64 = 5 * 1024 * 1024;
+/// The content type resumes must be uploaded as
+const RESUME_CONTENT_TYPE: &str = "application/pdf";
+/// The bytes every PDF starts with
+const PDF_MAGIC: &[u8] = b"%PDF-";
+
+results! {
+    UploadResumeResult {
+        /// The uploaded resume
+        resume: Resume,
+    }
+    DeleteResumeResult {
+        /// The deleted resume
+        resume: Resume,
+    }
+    CreateResumeBookLinkResult {
+        /// The link that was issued
+        link: ResumeBookLink,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "ResumeMutation")]
+impl Mutation {
+    /// Upload a resume with an in-progress application, replacing any existing one
+    ///
+    /// Resumes must be PDFs no larger than 5 MiB.
+    #[instrument(name = "Mutation::upload_resume", skip_all)]
+    async fn upload_resume(&self, ctx: &Context<'_>, file: Upload) -> Result<UploadResumeResult> {
+        const FIELD: &[&str] = &["uploadResume"];
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_role(ctx, UserRole::Participant)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        if let Some(error) = check_draft(FIELD, &scope.event, user.id, db).await? {
+            return Ok(error.into());
+        }
+
+        let upload = file.value(ctx)?;
+        if upload.content_type.as_deref() != Some(RESUME_CONTENT_TYPE) {
+            return Ok(UserError::new(&["file"], "must be a PDF").into());
+        }
+
+        let size = upload.size()?;
+        if size > MAX_RESUME_SIZE {
+            return Ok(UserError::new(&["file"], "must be at most 5 MiB").into());
+        }
+
+        let filename = upload.filename.clone();
+        let mut content = Vec::with_capacity(size as usize);
+        upload.into_read().read_to_end(&mut content)?;
+        if !content.starts_with(PDF_MAGIC) {
+            return Ok(UserError::new(&["file"], "must be a PDF").into());
+        }
+
+        let storage = ctx.data_unchecked::<Storage>();
+        let key = format!("resumes/{}", Uuid::new_v4());
+        storage.put(&key, content).await.map_err(StorageFailed)?;
+
+        // The upload is only referenced once the transaction commits, so it must be removed if
+        // saving fails
+        let saved = async {
+            let mut txn = db.begin().await?;
+            let previous = Resume::find(&scope.event, user.id, &mut txn)
+                .await
+                .extend()?;
+            let resume = Resume::save(
+                &scope.event,
+                user.id,
+                &key,
+                &filename,
+                RESUME_CONTENT_TYPE,
+                size as i32,
+                &mut txn,
+            )
+            .await
+            .extend()?;
+            txn.commit().await?;
+
+            Ok::<_, Error>((previous, resume))
+        }
+        .await;
+        let (previous, resume) = match saved {
+            Ok(saved) => saved,
+            Err(error) => {
+                if let Err(error) = storage.delete(&key).await {
+                    warn!(%error, key, "failed to remove unsaved resume");
+                }
+                return Err(error);
+            }
+        };
+
+        if let Some(previous) = previous {
+            if let Err(error) = storage.delete(&previous.key).await {
+                warn!(%error, key = previous.key, "failed to remove replaced resume");
+            }
+        }
+
+        Ok(resume.into())
+    }
+
+    /// Remove the resume from an in-progress application
+    #[instrument(name = "Mutation::delete_resume", skip_all)]
+    async fn delete_resume(&self, ctx: &Context<'_>) -> Result<DeleteResumeResult> {
+        const FIELD: &[&str] = &["deleteResume"];
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_role(ctx, UserRole::Participant)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        if let Some(error) = check_draft(FIELD, &scope.event, user.id, db).await? {
+            return Ok(error.into());
+        }
+
+        let Some(resume) = Resume::delete(&scope.event, user.id, db).await.extend()? else {
+            return Ok(UserError::new(FIELD, "no resume uploaded").into());
+        };
+
+        let storage = ctx.data_unchecked::<Storage>();
+        if let Err(error) = storage.delete(&resume.key).await {
+            warn!(%error, key = resume.key, "failed to remove deleted resume");
+        }
+
+        Ok(resume.into())
+    }
+
+    /// Issue a link for sponsors to download the event's resume book without signing in
+    ///
+    /// Links expire after 7 days and cannot be revoked individually.
+    #[instrument(name = "Mutation::create_resume_book_link", skip_all)]
+    async fn create_resume_book_link(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<CreateResumeBookLinkResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let links = ctx.data_unchecked::<ResumeBookLinks>();
+        let (token, expires_at) = links.issue(&scope.event);
+
+        Ok(ResumeBookLink {
+            path: format!("/resume-book?token={token}"),
+            expires_at,
+        }
+        .into())
+    }
+}
+
+/// A signed link to download an event's resume book
+#[derive(Debug, SimpleObject)]
+struct ResumeBookLink {
+    /// The path to download the resume book from, relative to the portal's address
+    path: String,
+    /// When the link stops working
+    expires_at: DateTime<Utc>,
+}
+
+/// Ensure the participant has an application that can still be changed
+async fn check_draft(
+    field: &'static [&'static str],
+    event: &str,
+    participant_id: i32,
+    db: &PgPool,
+) -> Result<Option<UserError>> {
+    if Application::exists(event, participant_id, db)
+        .await
+        .extend()?
+    {
+        return Ok(Some(UserError::new(field, "application already submitted")));
+    }
+
+    if !DraftApplication::exists(event, participant_id, db)
+        .await
+        .extend()?
+    {
+        return Ok(Some(UserError::new(
+            field,
+            "could not find a draft application",
+        )));
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,219 @@
+use super::{results, UserError};
+use async_graphql::{Context, InputObject, Object, Result, ResultExt};
+use context::{checks, UserRole};
+use database::{Application, PgPool, Review, RubricCriterion};
+use std::collections::HashSet;
+use tracing::instrument;
+use uuid::Uuid;
+
+results! {
+    SubmitReviewResult {
+        /// The submitted review
+        review: Review,
+    }
+    UpdateReviewResult {
+        /// The updated review
+        review: Review,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "ReviewMutation")]
+impl Mutation {
+    /// Submit a review of an application
+    ///
+    /// Each organizer can only review an application once, but can update their review afterwards.
+    #[instrument(name = "Mutation::submit_review", skip(self, ctx))]
+    async fn submit_review(
+        &self,
+        ctx: &Context<'_>,
+        input: SubmitReviewInput,
+    ) -> Result<SubmitReviewResult> {
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let mut txn = db.begin().await?;
+
+        if !Application::exists(&scope.event, input.id, &mut txn)
+            .await
+            .extend()?
+        {
+            return Ok(UserError::new(&["input", "id"], "application not found").into());
+        }
+
+        if Review::find(&scope.event, input.id, user.id, &mut txn)
+            .await
+            .extend()?
+            .is_some()
+        {
+            return Ok(UserError::new(&["input", "id"], "review already submitted").into());
+        }
+
+        let criteria = RubricCriterion::all(&scope.event, &mut txn)
+            .await
+            .extend()?;
+        let errors = validate_scores(&criteria, &input.scores);
+        if !errors.is_empty() {
+            return Ok(errors.into());
+        }
+
+        let mut review = Review::new(scope.event.clone(), input.id, user.id);
+        review.comment = input.comment.unwrap_or_default();
+        review.save(&mut txn).await.extend()?;
+
+        for score in &input.scores {
+            Review::set_score(
+                &scope.event,
+                input.id,
+                user.id,
+                score.criterion_id,
+                score.score,
+                &mut txn,
+            )
+            .await
+            .extend()?;
+        }
+
+        let review = Review::find(&scope.event, input.id, user.id, &mut txn)
+            .await
+            .extend()?
+            .expect("review must exist");
+
+        txn.commit().await?;
+
+        Ok(review.into())
+    }
+
+    /// Update an existing review of an application
+    ///
+    /// Only the provided scores are changed, any criteria that are omitted keep their existing
+    /// score.
+    #[instrument(name = "Mutation::update_review", skip(self, ctx))]
+    async fn update_review(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateReviewInput,
+    ) -> Result<UpdateReviewResult> {
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let mut txn = db.begin().await?;
+
+        let Some(mut review) = Review::find(&scope.event, input.id, user.id, &mut txn)
+            .await
+            .extend()?
+        else {
+            return Ok(UserError::new(&["input", "id"], "review not found").into());
+        };
+
+        let criteria = RubricCriterion::all(&scope.event, &mut txn)
+            .await
+            .extend()?;
+        let errors = validate_scores(&criteria, &input.scores);
+        if !errors.is_empty() {
+            return Ok(errors.into());
+        }
+
+        if let Some(comment) = input.comment {
+            review.comment = comment;
+            review.save(&mut txn).await.extend()?;
+        }
+
+        for score in &input.scores {
+            Review::set_score(
+                &scope.event,
+                input.id,
+                user.id,
+                score.criterion_id,
+                score.score,
+                &mut txn,
+            )
+            .await
+            .extend()?;
+        }
+
+        let review = Review::find(&scope.event, input.id, user.id, &mut txn)
+            .await
+            .extend()?
+            .expect("review must exist");
+
+        txn.commit().await?;
+
+        Ok(review.into())
+    }
+}
+
+/// Ensure each score is for a known criterion and within its bounds
+fn validate_scores(criteria: &[RubricCriterion], scores: &[ReviewScoreInput]) -> Vec<UserError> {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::with_capacity(scores.len());
+
+    for score in scores {
+        if !seen.insert(score.criterion_id) {
+            errors.push(UserError::new(
+                &["input", "scores"],
+                format!("criterion {} scored more than once", score.criterion_id),
+            ));
+            continue;
+        }
+
+        let Some(criterion) = criteria.iter().find(|c| c.id == score.criterion_id) else {
+            errors.push(UserError::new(
+                &["input", "scores"],
+                format!("unknown criterion {}", score.criterion_id),
+            ));
+            continue;
+        };
+
+        if score.score < 0 || score.score > criterion.max_score {
+            errors.push(UserError::new(
+                &["input", "scores"],
+                format!(
+                    "score for {:?} must be between 0 and {}",
+                    criterion.name, criterion.max_score
+                ),
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Input fields for submitting a review
+#[derive(Debug, InputObject)]
+struct SubmitReviewInput {
+    /// The ID of the application/participant being reviewed
+    id: i32,
+    /// The scores for each rubric criterion
+    #[graphql(default)]
+    scores: Vec<ReviewScoreInput>,
+    /// Free-form comments about the application
+    comment: Option<String>,
+}
+
+/// Input fields for updating a review
+#[derive(Debug, InputObject)]
+struct UpdateReviewInput {
+    /// The ID of the application/participant being reviewed
+    id: i32,
+    /// The scores to change for each rubric criterion
+    #[graphql(default)]
+    scores: Vec<ReviewScoreInput>,
+    /// Free-form comments about the application
+    comment: Option<String>,
+}
+
+/// The score for a single rubric criterion
+#[derive(Debug, InputObject)]
+struct ReviewScoreInput {
+    /// The ID of the criterion being scored
+    criterion_id: Uuid,
+    /// The score to give, between 0 and the criterion's maximum score
+    score: i32,
+}
@@ -0,0 +1,128 @@
+use super::{results, UserError};
+use async_graphql::{Context, InputObject, Object, Result, ResultExt};
+use context::{checks, UserRole};
+use database::{PgPool, RubricCriterion};
+use tracing::instrument;
+use uuid::Uuid;
+
+results! {
+    CreateRubricCriterionResult {
+        /// The created criterion
+        criterion: RubricCriterion,
+    }
+    UpdateRubricCriterionResult {
+        /// The updated criterion
+        criterion: RubricCriterion,
+    }
+    DeleteRubricCriterionResult {
+        /// The ID of the deleted criterion
+        deleted_id: Uuid,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "RubricCriterionMutation")]
+impl Mutation {
+    /// Add a criterion to the event's review rubric
+    #[instrument(name = "Mutation::create_rubric_criterion", skip(self, ctx))]
+    async fn create_rubric_criterion(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateRubricCriterionInput,
+    ) -> Result<CreateRubricCriterionResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let criterion = RubricCriterion::create(
+            &scope.event,
+            &input.name,
+            input.description.as_deref().unwrap_or_default(),
+            input.max_score,
+            db,
+        )
+        .await
+        .extend()?;
+
+        Ok(criterion.into())
+    }
+
+    /// Update a criterion in the event's review rubric
+    #[instrument(name = "Mutation::update_rubric_criterion", skip(self, ctx))]
+    async fn update_rubric_criterion(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateRubricCriterionInput,
+    ) -> Result<UpdateRubricCriterionResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let Some(mut criterion) = RubricCriterion::find(&scope.event, input.id, db)
+            .await
+            .extend()?
+        else {
+            return Ok(UserError::new(&["input", "id"], "criterion not found").into());
+        };
+
+        criterion
+            .update()
+            .override_name(input.name)
+            .override_description(input.description)
+            .override_max_score(input.max_score)
+            .save(db)
+            .await
+            .extend()?;
+
+        Ok(criterion.into())
+    }
+
+    /// Remove a criterion from the event's review rubric
+    ///
+    /// Any scores given for the criterion are also removed.
+    #[instrument(name = "Mutation::delete_rubric_criterion", skip(self, ctx))]
+    async fn delete_rubric_criterion(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> Result<DeleteRubricCriterionResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        RubricCriterion::delete(&scope.event, id, db)
+            .await
+            .extend()?;
+
+        Ok(id.into())
+    }
+}
+
+/// Input fields for creating a rubric criterion
+#[derive(Debug, InputObject)]
+struct CreateRubricCriterionInput {
+    /// A short name for the criterion
+    name: String,
+    /// Guidance for reviewers on how to score the criterion
+    description: Option<String>,
+    /// The highest score that can be given, inclusive
+    #[graphql(validator(minimum = 1))]
+    max_score: i32,
+}
+
+/// Input fields for updating a rubric criterion
+#[derive(Debug, InputObject)]
+struct UpdateRubricCriterionInput {
+    /// The ID of the criterion
+    id: Uuid,
+
+    /// A short name for the criterion
+    name: Option<String>,
+    /// Guidance for reviewers on how to score the criterion
+    description: Option<String>,
+    /// The highest score that can be given, inclusive
+    #[graphql(validator(minimum = 1))]
+    max_score: Option<i32>,
+}
@@ -0,0 +1,155 @@
+use super::{results, UserError};
+use crate::webhooks::{self, EVENT_TYPES};
+use async_graphql::{Context, InputObject, Object, Result, ResultExt, SimpleObject};
+use context::{checks, UserRole};
+use database::{PgPool, WebhookEndpoint};
+use reqwest::Url;
+use tracing::instrument;
+use uuid::Uuid;
+
+results! {
+    CreateWebhookEndpointResult {
+        /// The created endpoint
+        endpoint: IssuedWebhookEndpoint,
+    }
+    RotateWebhookEndpointSecretResult {
+        /// The updated endpoint
+        endpoint: IssuedWebhookEndpoint,
+    }
+    DeleteWebhookEndpointResult {
+        /// The ID of the deleted endpoint
+        deleted_id: Uuid,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Mutation;
+
+#[Object(name = "WebhookEndpointMutation")]
+impl Mutation {
+    /// Add an endpoint to deliver the event's webhooks to
+    ///
+    /// Endpoints are only used when webhooks are dispatched by the portal itself. A signing secret
+    /// is generated for the endpoint, which is only returned this once.
+    #[instrument(name = "Mutation::create_webhook_endpoint", skip(self, ctx))]
+    async fn create_webhook_endpoint(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateWebhookEndpointInput,
+    ) -> Result<CreateWebhookEndpointResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let mut user_errors = Vec::new();
+        match Url::parse(&input.url) {
+            Ok(url) => {
+                if let Err(error) = webhooks::check_url(&url).await {
+                    user_errors.push(UserError::new(&["input", "url"], error));
+                }
+            }
+            Err(_) => user_errors.push(UserError::new(&["input", "url"], "must be an http(s) url")),
+        }
+
+        let event_types = input.event_types.unwrap_or_default();
+        for event_type in &event_types {
+            if !EVENT_TYPES.contains(&event_type.as_str()) {
+                user_errors.push(UserError::new(
+                    &["input", "eventTypes"],
+                    format!("unknown event type {event_type:?}"),
+                ));
+            }
+        }
+
+        if !user_errors.is_empty() {
+            return Ok(user_errors.into());
+        }
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let endpoint = WebhookEndpoint::create(
+            &scope.event,
+            &input.url,
+            &webhooks::generate_secret(),
+            &event_types,
+            db,
+        )
+        .await
+        .extend()?;
+
+        Ok(IssuedWebhookEndpoint::from(endpoint).into())
+    }
+
+    /// Replace the secret used to sign an endpoint's webhooks
+    ///
+    /// The new secret is only returned this once, and the previous secret stops working
+    /// immediately.
+    #[instrument(name = "Mutation::rotate_webhook_endpoint_secret", skip(self, ctx))]
+    async fn rotate_webhook_endpoint_secret(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> Result<RotateWebhookEndpointSecretResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let Some(endpoint) =
+            WebhookEndpoint::rotate_secret(&scope.event, id, &webhooks::generate_secret(), db)
+                .await
+                .extend()?
+        else {
+            return Ok(UserError::new(&["id"], "endpoint not found").into());
+        };
+
+        Ok(IssuedWebhookEndpoint::from(endpoint).into())
+    }
+
+    /// Stop delivering the event's webhooks to an endpoint
+    #[instrument(name = "Mutation::delete_webhook_endpoint", skip(self, ctx))]
+    async fn delete_webhook_endpoint(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> Result<DeleteWebhookEndpointResult> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        if !WebhookEndpoint::delete(&scope.event, id, db)
+            .await
+            .extend()?
+        {
+            return Ok(UserError::new(&["id"], "endpoint not found").into());
+        }
+
+        Ok(id.into())
+    }
+}
+
+/// A webhook endpoint along with the secret that was just issued for it
+#[derive(Debug, SimpleObject)]
+struct IssuedWebhookEndpoint {
+    #[graphql(flatten)]
+    endpoint: WebhookEndpoint,
+    /// The secret used to sign the webhooks, prefixed with `whsec_`
+    ///
+    /// The secret cannot be retrieved again, so it must be stored now.
+    secret: String,
+}
+
+impl From<WebhookEndpoint> for IssuedWebhookEndpoint {
+    fn from(endpoint: WebhookEndpoint) -> Self {
+        Self {
+            secret: endpoint.secret.clone(),
+            endpoint,
+        }
+    }
+}
+
+/// Input fields for creating a webhook endpoint
+#[derive(Debug, InputObject)]
+struct CreateWebhookEndpointInput {
+    /// Where webhooks are delivered to
+    url: String,
+    /// The types of webhooks to deliver, defaults to all types
+    event_types: Option<Vec<String>>,
+}
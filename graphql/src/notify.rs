@@ -0,0 +1,62 @@
+use crate::check_in_token::CheckInTokens;
+use database::{Application, ApplicationStatus, Email, EventSettings, OutboxEmail, PgConnection};
+use mail::{AcceptedModel, ApplicationModel, Template};
+use tracing::instrument;
+
+/// Queue the email notifying a participant about their application's current status
+#[instrument(name = "notify::application_status", skip_all)]
+pub(crate) async fn application_status(
+    conn: &mut PgConnection,
+    tokens: &CheckInTokens,
+    application: &Application,
+    recipient: &str,
+) -> Result<(), database::Error> {
+    let settings = EventSettings::find(&application.event, &mut *conn).await?;
+    let email = Email::find(application.participant_id, &mut *conn).await?;
+    let (first_name, last_name) = email
+        .map(|email| (email.first_name, email.last_name))
+        .unwrap_or_default();
+
+    let model = ApplicationModel {
+        event: application.event.clone(),
+        event_name: settings.display_name().to_owned(),
+        participant_id: application.participant_id,
+        first_name,
+        last_name,
+    };
+
+    let template = match application.status {
+        ApplicationStatus::Pending => Template::Pending(model),
+        ApplicationStatus::Waitlisted => Template::Waitlisted(model),
+        ApplicationStatus::Rejected => Template::Rejected(model),
+        ApplicationStatus::Accepted => {
+            let rsvp_deadline = Application::find_rsvp_deadline(
+                &application.event,
+                application.participant_id,
+                &mut *conn,
+            )
+            .await?;
+
+            let check_in_token = tokens.issue(&application.event, application.participant_id);
+
+            Template::Accepted(AcceptedModel {
+                application: model,
+                rsvp_deadline,
+                check_in_qr_code_url: tokens.qr_code_url(&application.event, &check_in_token),
+                check_in_token,
+            })
+        }
+        ApplicationStatus::Confirmed => Template::Confirmed(model),
+        ApplicationStatus::Declined => Template::Declined(model),
+        ApplicationStatus::Withdrawn => Template::Withdrawn(model),
+    };
+
+    OutboxEmail::enqueue(
+        &application.event,
+        template.alias(),
+        &template.model(),
+        recipient,
+        conn,
+    )
+    .await
+}
@@ -1,37 +1,57 @@
-use crate::errors::Forbidden;
-use async_graphql::{Context, Object, Result, ResultExt};
+use crate::{
+    check_in_token::{self, CheckInTokens},
+    errors::{Forbidden, NoWebhookDashboard},
+    kiosk_signer::KioskSigner,
+    webhooks,
+};
+use async_graphql::{
+    connection::{Connection, ConnectionNameType, CursorType, Edge, EmptyFields},
+    Context, Error, Object, OutputType, Result, ResultExt, SimpleObject,
+};
+use chrono::{DateTime, Utc};
 use context::{checks, UserRole};
-use database::{Application, DraftApplication, PgPool};
-use svix::api::{AppPortalAccessIn, Svix};
+use database::{
+    Announcement, Application, ApplicationCursor, ApplicationFilter, ApplicationOrder,
+    ApplicationQuestion, CheckIn, Checkpoint, DraftApplication, EmailSettings, EventSettings,
+    OutboxEmail, PgPool, RubricCriterion, WebhookEndpoint,
+};
+use serde::Serialize;
+use std::collections::HashSet;
 use tracing::instrument;
-
-/// When the webhook dashboard should expire (1 day in seconds)
-const WEBHOOK_DASHBOARD_EXPIRY: i32 = 60 * 60 * 24;
+use uuid::Uuid;
 
 pub struct Query;
 
 #[Object]
 impl Query {
     /// Get the URL for the webhook portal
+    ///
+    /// When webhooks are dispatched by the portal itself, there is no portal, so an error is
+    /// returned and the endpoints are managed using `webhookEndpoints` instead.
     #[graphql(shareable)]
     #[instrument(name = "Query::webhook_dashboard_url", skip_all)]
     async fn webhook_dashboard_url(&self, ctx: &Context<'_>) -> Result<String> {
         let event = checks::is_event(ctx)?;
         let role = checks::has_at_least_role(ctx, UserRole::Organizer)?;
 
-        let options = AppPortalAccessIn {
-            expiry: Some(WEBHOOK_DASHBOARD_EXPIRY),
-            read_only: Some(role == UserRole::Organizer),
-            ..AppPortalAccessIn::default()
-        };
-
-        let svix = ctx.data_unchecked::<Svix>();
-        let dashboard = svix
-            .authentication()
-            .app_portal_access(event.event.clone(), options, None)
+        let webhooks = ctx.data_unchecked::<webhooks::Client>();
+        let url = webhooks
+            .dashboard_url(&event.event, role == UserRole::Organizer)
             .await?;
 
-        Ok(dashboard.url)
+        url.ok_or_else(|| NoWebhookDashboard.into())
+    }
+
+    /// Get the endpoints the event's webhooks are delivered to when dispatched by the portal
+    #[instrument(name = "Query::webhook_endpoints", skip_all)]
+    async fn webhook_endpoints(&self, ctx: &Context<'_>) -> Result<Vec<WebhookEndpoint>> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let endpoints = WebhookEndpoint::all(&scope.event, db).await.extend()?;
+
+        Ok(endpoints)
     }
 
     /// Get a submitted application
@@ -61,16 +81,159 @@ impl Query {
         Ok(application)
     }
 
-    /// Get all submitted applications
-    #[instrument(name = "Query::applications", skip_all)]
-    async fn applications(&self, ctx: &Context<'_>) -> Result<Vec<Application>> {
+    /// Get a page of submitted applications
+    #[instrument(name = "Query::applications", skip(self, ctx))]
+    async fn applications(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<ApplicationFilter>,
+        #[graphql(default)] order: ApplicationOrder,
+        after: Option<String>,
+        #[graphql(default = 25, validator(minimum = 1, maximum = 100))] first: i32,
+    ) -> Result<Connection<ApplicationCursor, Application, ApplicationConnectionFields>> {
         let scope = checks::is_event(ctx)?;
         checks::has_at_least_role(ctx, UserRole::Organizer)?;
 
+        let filter = filter.unwrap_or_default();
+        let after = after
+            .as_deref()
+            .map(ApplicationCursor::decode_cursor)
+            .transpose()
+            .map_err(Error::new)?;
+
         let db = ctx.data_unchecked::<PgPool>();
-        let applications = Application::all(&scope.event, db).await.extend()?;
+        let mut applications = Application::list(
+            &scope.event,
+            &filter,
+            order,
+            after,
+            Some(i64::from(first) + 1),
+            db,
+        )
+        .await
+        .extend()?;
 
-        Ok(applications)
+        let has_next_page = applications.len() > first as usize;
+        applications.truncate(first as usize);
+
+        let mut connection = Connection::with_additional_fields(
+            after.is_some(),
+            has_next_page,
+            ApplicationConnectionFields {
+                event: scope.event.clone(),
+                filter,
+            },
+        );
+        connection
+            .edges
+            .extend(applications.into_iter().map(|application| {
+                Edge::new(ApplicationCursor::new(&application, order), application)
+            }));
+
+        Ok(connection)
+    }
+
+    /// Get a page of the participants who can attend the event, along with their check ins
+    ///
+    /// Only participants who can check in are included, so those who have not confirmed their
+    /// attendance yet are left out when the event requires confirming first.
+    #[instrument(name = "Query::check_in_roster", skip(self, ctx))]
+    async fn check_in_roster(
+        &self,
+        ctx: &Context<'_>,
+        checked_in: Option<bool>,
+        #[graphql(default)] order: ApplicationOrder,
+        after: Option<String>,
+        #[graphql(default = 25, validator(minimum = 1, maximum = 100))] first: i32,
+    ) -> Result<CheckInRoster> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let settings = EventSettings::find(&scope.event, db).await.extend()?;
+        let confirmed_only = settings.check_in_requires_rsvp;
+
+        let filter = ApplicationFilter {
+            accepted: Some(true),
+            confirmed_only,
+            checked_in,
+            ..ApplicationFilter::default()
+        };
+        let after = after
+            .as_deref()
+            .map(ApplicationCursor::decode_cursor)
+            .transpose()
+            .map_err(Error::new)?;
+
+        let mut applications = Application::list(
+            &scope.event,
+            &filter,
+            order,
+            after,
+            Some(i64::from(first) + 1),
+            db,
+        )
+        .await
+        .extend()?;
+
+        let has_next_page = applications.len() > first as usize;
+        applications.truncate(first as usize);
+
+        let mut connection = Connection::with_additional_fields(
+            after.is_some(),
+            has_next_page,
+            CheckInRosterFields {
+                event: scope.event.clone(),
+                confirmed_only,
+            },
+        );
+        connection
+            .edges
+            .extend(applications.into_iter().map(|application| {
+                Edge::new(ApplicationCursor::new(&application, order), application)
+            }));
+
+        Ok(connection)
+    }
+
+    /// Get the code a participant checks in to the event with
+    ///
+    /// Codes are only issued to participants whose applications were accepted.
+    #[instrument(name = "Query::check_in_code", skip(self, ctx))]
+    async fn check_in_code(
+        &self,
+        ctx: &Context<'_>,
+        id: Option<i32>,
+    ) -> Result<Option<CheckInCode>> {
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+
+        let id = match id {
+            Some(id) if id != user.id => {
+                checks::has_at_least_role(ctx, UserRole::Organizer)?;
+                id
+            }
+            _ => {
+                checks::has_role(ctx, UserRole::Participant)?;
+                user.id
+            }
+        };
+
+        let db = ctx.data_unchecked::<PgPool>();
+        if !Application::accepted_exists(&scope.event, id, db)
+            .await
+            .extend()?
+        {
+            return Ok(None);
+        }
+
+        let tokens = ctx.data_unchecked::<CheckInTokens>();
+        let token = tokens.issue(&scope.event, id);
+
+        Ok(Some(CheckInCode {
+            svg: check_in_token::render_svg(&token),
+            token,
+        }))
     }
 
     /// Get an in-progress application
@@ -105,4 +268,360 @@ impl Query {
 
         Ok(draft)
     }
+
+    /// Get the questions participants answer as part of their application
+    #[instrument(name = "Query::application_questions", skip_all)]
+    async fn application_questions(&self, ctx: &Context<'_>) -> Result<Vec<ApplicationQuestion>> {
+        let user = checks::is_authenticated(ctx)?;
+        let scope = checks::is_event(ctx)?;
+
+        // must be part of event
+        if user.role.is_none() {
+            return Err(Forbidden.into());
+        }
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let questions = ApplicationQuestion::all(&scope.event, db).await.extend()?;
+
+        Ok(questions)
+    }
+
+    /// Get the settings for the event
+    #[instrument(name = "Query::event_settings", skip_all)]
+    async fn event_settings(&self, ctx: &Context<'_>) -> Result<EventSettings> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let settings = EventSettings::find(&scope.event, db).await.extend()?;
+
+        Ok(settings)
+    }
+
+    /// Get all the announcements sent for the event, newest first
+    #[instrument(name = "Query::announcements", skip_all)]
+    async fn announcements(&self, ctx: &Context<'_>) -> Result<Vec<Announcement>> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let announcements = Announcement::all(&scope.event, db).await.extend()?;
+
+        Ok(announcements)
+    }
+
+    /// Get an announcement sent for the event
+    #[instrument(name = "Query::announcement", skip(self, ctx))]
+    async fn announcement(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<Announcement>> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let announcement = Announcement::find(&scope.event, id, db).await.extend()?;
+
+        Ok(announcement)
+    }
+
+    /// Preview how many participants an announcement sent to the segment would reach
+    #[instrument(name = "Query::announcement_recipient_count", skip(self, ctx))]
+    async fn announcement_recipient_count(
+        &self,
+        ctx: &Context<'_>,
+        segment: Option<ApplicationFilter>,
+    ) -> Result<i64> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let count = Announcement::count_segment(&scope.event, &segment.unwrap_or_default(), db)
+            .await
+            .extend()?;
+
+        Ok(count)
+    }
+
+    /// Get how emails for the event are sent
+    #[instrument(name = "Query::email_settings", skip_all)]
+    async fn email_settings(&self, ctx: &Context<'_>) -> Result<EmailSettings> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let settings = EmailSettings::find(&scope.event, db).await.extend()?;
+
+        Ok(settings)
+    }
+
+    /// Get the emails for the event that could not be delivered
+    #[instrument(name = "Query::failed_emails", skip_all)]
+    async fn failed_emails(&self, ctx: &Context<'_>) -> Result<Vec<OutboxEmail>> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let emails = OutboxEmail::failed(&scope.event, db).await.extend()?;
+
+        Ok(emails)
+    }
+
+    /// Get who is currently at the event, in the order they arrived
+    #[instrument(name = "Query::currently_present", skip_all)]
+    async fn currently_present(&self, ctx: &Context<'_>) -> Result<CurrentlyPresent> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let participants = CheckIn::present(&scope.event, db).await.extend()?;
+        let checked_in_count = CheckIn::count(&scope.event, false, db).await.extend()?;
+
+        Ok(CurrentlyPresent {
+            count: participants.len() as i64,
+            checked_in_count,
+            participants,
+        })
+    }
+
+    /// Get a signed snapshot of who can check in, for kiosks to use while offline
+    ///
+    /// The payload contains each eligible participant's check-in token, so kiosks can identify
+    /// participants from their QR codes without a connection. Anything recorded offline is
+    /// uploaded afterwards with `syncKioskCheckIns`.
+    #[instrument(name = "Query::kiosk_snapshot", skip_all)]
+    async fn kiosk_snapshot(&self, ctx: &Context<'_>) -> Result<KioskSnapshot> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let settings = EventSettings::find(&scope.event, db).await.extend()?;
+        let eligible =
+            Application::eligible_for_check_in(&scope.event, settings.check_in_requires_rsvp, db)
+                .await
+                .extend()?;
+        let present = CheckIn::present(&scope.event, db)
+            .await
+            .extend()?
+            .into_iter()
+            .map(|check_in| check_in.participant_id)
+            .collect::<HashSet<_>>();
+        let checkpoints = Checkpoint::all(&scope.event, db).await.extend()?;
+
+        let tokens = ctx.data_unchecked::<CheckInTokens>();
+        let generated_at = Utc::now();
+        let payload = KioskSnapshotPayload {
+            event: &scope.event,
+            generated_at,
+            participants: eligible
+                .into_iter()
+                .map(|id| KioskParticipant {
+                    id,
+                    token: tokens.issue(&scope.event, id),
+                    present: present.contains(&id),
+                })
+                .collect(),
+            checkpoints: checkpoints
+                .iter()
+                .map(|checkpoint| KioskCheckpoint {
+                    slug: &checkpoint.slug,
+                    once_only: checkpoint.once_only,
+                    capacity: checkpoint.capacity,
+                })
+                .collect(),
+        };
+        let payload = serde_json::to_string(&payload)?;
+
+        let signer = ctx.data_unchecked::<KioskSigner>();
+        Ok(KioskSnapshot {
+            signature: signer.sign(payload.as_bytes()),
+            payload,
+            generated_at,
+        })
+    }
+
+    /// Get the public key that kiosk snapshots are signed with
+    ///
+    /// Kiosks should store the key when they are set up, then verify every snapshot they download
+    /// against it.
+    #[instrument(name = "Query::kiosk_public_key", skip_all)]
+    async fn kiosk_public_key(&self, ctx: &Context<'_>) -> Result<String> {
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let signer = ctx.data_unchecked::<KioskSigner>();
+        Ok(signer.public_key())
+    }
+
+    /// Get the places participants are scanned at during the event
+    #[instrument(name = "Query::checkpoints", skip_all)]
+    async fn checkpoints(&self, ctx: &Context<'_>) -> Result<Vec<Checkpoint>> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let checkpoints = Checkpoint::all(&scope.event, db).await.extend()?;
+
+        Ok(checkpoints)
+    }
+
+    /// Get a place participants are scanned at during the event
+    #[instrument(name = "Query::checkpoint", skip(self, ctx))]
+    async fn checkpoint(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<Checkpoint>> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let checkpoint = Checkpoint::find(&scope.event, id, db).await.extend()?;
+
+        Ok(checkpoint)
+    }
+
+    /// Get the criteria applications are scored against during review
+    #[instrument(name = "Query::rubric_criteria", skip_all)]
+    async fn rubric_criteria(&self, ctx: &Context<'_>) -> Result<Vec<RubricCriterion>> {
+        let scope = checks::is_event(ctx)?;
+        checks::has_at_least_role(ctx, UserRole::Organizer)?;
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let criteria = RubricCriterion::all(&scope.event, db).await.extend()?;
+
+        Ok(criteria)
+    }
+}
+
+/// A participant's check-in token, along with its QR code
+#[derive(Debug, SimpleObject)]
+pub struct CheckInCode {
+    /// The signed token identifying the participant
+    token: String,
+    /// The token rendered as a QR code, in SVG format
+    svg: String,
+}
+
+/// The participants who are currently at the event
+#[derive(Debug, SimpleObject)]
+pub struct CurrentlyPresent {
+    /// The number of participants currently at the event
+    count: i64,
+    /// The number of participants who have checked in at any point, including those who left
+    checked_in_count: i64,
+    /// The check ins of the participants currently at the event
+    participants: Vec<CheckIn>,
+}
+
+/// A signed copy of who can check in, for kiosks to use while offline
+#[derive(Debug, SimpleObject)]
+pub struct KioskSnapshot {
+    /// The snapshot as JSON, containing the event, when it was generated, the eligible
+    /// participants with their check-in tokens and whether they are present, and the checkpoints
+    payload: String,
+    /// The portal's Ed25519 signature of the payload, encoded as URL-safe base64
+    ///
+    /// Verified using the key from `kioskPublicKey`.
+    signature: String,
+    /// When the snapshot was generated
+    generated_at: DateTime<Utc>,
+}
+
+/// The contents of a kiosk snapshot
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KioskSnapshotPayload<'s> {
+    event: &'s str,
+    generated_at: DateTime<Utc>,
+    participants: Vec<KioskParticipant>,
+    checkpoints: Vec<KioskCheckpoint<'s>>,
+}
+
+/// A participant who can check in, as seen by a kiosk
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KioskParticipant {
+    id: i32,
+    token: String,
+    present: bool,
+}
+
+/// A checkpoint's rules, as seen by a kiosk
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KioskCheckpoint<'s> {
+    slug: &'s str,
+    once_only: bool,
+    capacity: Option<i32>,
+}
+
+/// Additional fields for paginating through applications
+pub struct ApplicationConnectionFields {
+    event: String,
+    filter: ApplicationFilter,
+}
+
+#[Object]
+impl ApplicationConnectionFields {
+    /// The total number of applications matching the filter
+    #[instrument(name = "ApplicationConnection::total_count", skip_all)]
+    async fn total_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        let db = ctx.data_unchecked::<PgPool>();
+        let count = Application::count(&self.event, &self.filter, db)
+            .await
+            .extend()?;
+
+        Ok(count)
+    }
+}
+
+/// A page of the participants who can attend the event
+pub type CheckInRoster =
+    Connection<ApplicationCursor, Application, CheckInRosterFields, EmptyFields, CheckInRosterName>;
+
+/// Names the check-in roster's connection type, distinguishing it from other application listings
+pub struct CheckInRosterName;
+
+impl ConnectionNameType for CheckInRosterName {
+    fn type_name<T: OutputType>() -> String {
+        String::from("CheckInRosterConnection")
+    }
+}
+
+/// Additional fields for paginating through the check-in roster
+pub struct CheckInRosterFields {
+    event: String,
+    /// Whether participants must confirm their attendance before checking in
+    confirmed_only: bool,
+}
+
+#[Object]
+impl CheckInRosterFields {
+    /// The number of participants who can check in
+    #[instrument(name = "CheckInRosterConnection::accepted_count", skip_all)]
+    async fn accepted_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        let filter = ApplicationFilter {
+            accepted: Some(true),
+            confirmed_only: self.confirmed_only,
+            ..ApplicationFilter::default()
+        };
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let count = Application::count(&self.event, &filter, db)
+            .await
+            .extend()?;
+
+        Ok(count)
+    }
+
+    /// The number of participants who can check in and already did, including those who left
+    #[instrument(name = "CheckInRosterConnection::checked_in_count", skip_all)]
+    async fn checked_in_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        let filter = ApplicationFilter {
+            accepted: Some(true),
+            confirmed_only: self.confirmed_only,
+            checked_in: Some(true),
+            ..ApplicationFilter::default()
+        };
+
+        let db = ctx.data_unchecked::<PgPool>();
+        let count = Application::count(&self.event, &filter, db)
+            .await
+            .extend()?;
+
+        Ok(count)
+    }
 }
@@ -0,0 +1,63 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+
+/// How long a resume book link can be used for
+const LINK_LIFETIME: Duration = Duration::days(7);
+
+/// Issues and verifies the signed links sponsors use to download an event's resume book
+///
+/// Tokens are of the form `{expires_at}.{signature}`, where `expires_at` is a unix timestamp, and
+/// are only valid for the event they were issued for. Links cannot be revoked individually, the
+/// secret must be replaced to invalidate them all.
+#[derive(Clone)]
+pub struct ResumeBookLinks {
+    key: Arc<[u8]>,
+}
+
+impl ResumeBookLinks {
+    /// Create a new signer using the secret
+    pub fn new(secret: &str) -> Self {
+        Self {
+            key: secret.as_bytes().into(),
+        }
+    }
+
+    /// Issue a token for downloading an event's resume book, returning when it expires
+    pub fn issue(&self, event: &str) -> (String, DateTime<Utc>) {
+        let expires_at = Utc::now() + LINK_LIFETIME;
+        let timestamp = expires_at.timestamp();
+        let signature = self.mac(event, timestamp).finalize().into_bytes();
+        let signature = URL_SAFE_NO_PAD.encode(signature);
+
+        (format!("{timestamp}.{signature}"), expires_at)
+    }
+
+    /// Check that a token was issued for the event and has not expired yet
+    pub fn verify(&self, event: &str, token: &str) -> bool {
+        let Some((timestamp, signature)) = token.split_once('.') else {
+            return false;
+        };
+        let Ok(timestamp) = timestamp.parse::<i64>() else {
+            return false;
+        };
+        let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+            return false;
+        };
+        if timestamp < Utc::now().timestamp() {
+            return false;
+        }
+
+        self.mac(event, timestamp).verify_slice(&signature).is_ok()
+    }
+
+    /// Start the signature for an event's link
+    fn mac(&self, event: &str, timestamp: i64) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.key).expect("hmac accepts keys of any length");
+        mac.update(format!("resume-book:{event}.{timestamp}").as_bytes());
+        mac
+    }
+}
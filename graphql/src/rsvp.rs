@@ -0,0 +1,54 @@
+use crate::{
+    check_in_token::CheckInTokens,
+    notify,
+    webhooks::{self, StatusChanged},
+};
+use database::{Application, ApplicationStatus, Email, PgPool};
+use tracing::instrument;
+
+/// Decline the applications of accepted participants who did not confirm their attendance before
+/// their event's RSVP deadline
+#[instrument(name = "rsvp::decline_expired", skip_all)]
+pub async fn decline_expired(
+    db: &PgPool,
+    tokens: &CheckInTokens,
+) -> Result<Vec<Application>, database::Error> {
+    let mut txn = db.begin().await?;
+
+    let applications = Application::decline_expired_rsvps(&mut txn).await?;
+    if applications.is_empty() {
+        return Ok(applications);
+    }
+
+    let ids = applications
+        .iter()
+        .map(|application| application.participant_id)
+        .collect::<Vec<_>>();
+    let emails = Email::find_many(&ids, &mut txn).await?;
+    for application in &applications {
+        let Some(email) = emails
+            .iter()
+            .find(|email| email.participant_id == application.participant_id)
+        else {
+            continue;
+        };
+
+        notify::application_status(&mut txn, tokens, application, &email.address).await?;
+    }
+    for application in &applications {
+        webhooks::enqueue(
+            &mut txn,
+            "application.status_changed",
+            &application.event,
+            &StatusChanged {
+                previous_status: ApplicationStatus::Accepted,
+                application,
+            },
+        )
+        .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(applications)
+}
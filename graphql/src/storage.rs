@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use std::{io, sync::Arc};
+
+mod local;
+
+pub use local::LocalStorage;
+
+/// A place where uploaded files are kept
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// Store a file, replacing any existing file with the same key
+    async fn put(&self, key: &str, content: Vec<u8>) -> io::Result<()>;
+
+    /// Retrieve a file, if it exists
+    async fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>>;
+
+    /// Remove a file, succeeding if it does not exist
+    async fn delete(&self, key: &str) -> io::Result<()>;
+}
+
+/// A storage client keeping files in the configured backend
+#[derive(Clone)]
+pub struct Storage {
+    backend: Arc<dyn StorageBackend>,
+}
+
+impl Storage {
+    /// Create a new client keeping files in the backend
+    pub fn new<B: StorageBackend + 'static>(backend: B) -> Self {
+        Self {
+            backend: Arc::new(backend),
+        }
+    }
+
+    /// Store a file, replacing any existing file with the same key
+    pub async fn put(&self, key: &str, content: Vec<u8>) -> io::Result<()> {
+        self.backend.put(key, content).await
+    }
+
+    /// Retrieve a file, if it exists
+    pub async fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        self.backend.get(key).await
+    }
+
+    /// Remove a file, succeeding if it does not exist
+    pub async fn delete(&self, key: &str) -> io::Result<()> {
+        self.backend.delete(key).await
+    }
+}
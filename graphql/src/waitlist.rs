@@ -0,0 +1,92 @@
+use crate::{
+    check_in_token::CheckInTokens,
+    notify,
+    webhooks::{self, StatusChanged},
+};
+use database::{Application, ApplicationFilter, ApplicationStatus, Email, EventSettings, PgPool};
+use tracing::instrument;
+
+/// Promote applications from an event's waitlist, in the order they were waitlisted
+///
+/// When no count is provided, enough applications are promoted to fill the event's remaining
+/// capacity. Events without a capacity only have applications promoted when a count is provided.
+/// The count is capped at the remaining capacity, so an event is never filled past it.
+#[instrument(name = "waitlist::promote", skip(db, tokens))]
+pub async fn promote(
+    db: &PgPool,
+    tokens: &CheckInTokens,
+    event: &str,
+    count: Option<i64>,
+) -> Result<Vec<Application>, database::Error> {
+    let mut txn = db.begin().await?;
+
+    // prevents concurrent promotions from accepting more participants than there is capacity for
+    let settings = EventSettings::find_for_update(event, &mut txn).await?;
+
+    let remaining = match settings.and_then(|settings| settings.capacity) {
+        Some(capacity) => {
+            let mut accepted = 0;
+            for status in [ApplicationStatus::Accepted, ApplicationStatus::Confirmed] {
+                let filter = ApplicationFilter {
+                    status: Some(status),
+                    ..ApplicationFilter::default()
+                };
+                accepted += Application::count(event, &filter, &mut txn).await?;
+            }
+
+            Some(i64::from(capacity) - accepted)
+        }
+        None => None,
+    };
+
+    let count = match (count, remaining) {
+        (Some(count), Some(remaining)) => count.min(remaining),
+        (Some(count), None) => count,
+        (None, Some(remaining)) => remaining,
+        (None, None) => return Ok(Vec::with_capacity(0)),
+    };
+    if count <= 0 {
+        return Ok(Vec::with_capacity(0));
+    }
+
+    let mut applications = Application::waitlist(event, count, &mut txn).await?;
+    for application in &mut applications {
+        application
+            .update()
+            .status(ApplicationStatus::Accepted)
+            .save(&mut txn)
+            .await?;
+    }
+
+    let ids = applications
+        .iter()
+        .map(|application| application.participant_id)
+        .collect::<Vec<_>>();
+    let emails = Email::find_many(&ids, &mut txn).await?;
+    for application in &applications {
+        let Some(email) = emails
+            .iter()
+            .find(|email| email.participant_id == application.participant_id)
+        else {
+            continue;
+        };
+
+        notify::application_status(&mut txn, tokens, application, &email.address).await?;
+    }
+    for application in &applications {
+        webhooks::enqueue(
+            &mut txn,
+            "application.status_changed",
+            event,
+            &StatusChanged {
+                previous_status: ApplicationStatus::Waitlisted,
+                application,
+            },
+        )
+        .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(applications)
+}
@@ -1,10 +1,38 @@
-use chrono::{DateTime, Utc};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use database::{Application, ApplicationStatus, OutboxWebhook, PgConnection, PgPool};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::Serialize;
 use std::sync::Arc;
-use svix::api::{MessageIn, Svix};
-use tracing::{error, info_span, instrument, Instrument};
+use tracing::{instrument, warn};
+
+mod error;
+mod http;
+mod svix;
+
+pub use self::svix::SvixSink;
+pub use error::Error;
+pub use http::HttpSink;
+pub(crate) use http::{check_url, generate_secret};
+
+/// The types of webhooks that can be published
+pub const EVENT_TYPES: [&str; 7] = [
+    "application.submitted",
+    "application.status_changed",
+    "application.updated",
+    "application.deleted",
+    "participant.checked_in",
+    "participant.checked_out",
+    "draft_application.started",
+];
+
+/// The delay before the first retry, doubled after each subsequent failure
+const BASE_DELAY_SECONDS: i64 = 30;
+/// The longest delay between retries
+const MAX_DELAY_SECONDS: i64 = 60 * 60;
+/// How long a batch is reserved for a worker before other workers can attempt it
+const CLAIM_SECONDS: i64 = 5 * 60;
 
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -22,13 +50,77 @@ pub struct Payload<'p, T> {
     pub at: DateTime<Utc>,
 }
 
-/// Send a webhook event
-#[instrument(name = "webhook::send", skip(client, object))]
-pub async fn send<T>(client: &Arc<Svix>, event_type: &str, event_slug: &str, object: &T)
+/// The object sent with `application.status_changed` webhooks
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct StatusChanged<'a> {
+    /// The status the application had before it was changed
+    pub previous_status: ApplicationStatus,
+    /// The application after its status was changed
+    pub application: &'a Application,
+}
+
+/// The object sent with `application.deleted` webhooks
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct Deleted {
+    /// The ID of the participant whose application was deleted
+    pub participant_id: i32,
+}
+
+/// A destination that webhooks are published to
+#[async_trait]
+pub trait WebhookSink: Send + Sync {
+    /// Publish a queued webhook to all the event's endpoints
+    async fn publish(&self, webhook: &OutboxWebhook, db: &PgPool) -> Result<(), Error>;
+
+    /// Get a link to a dashboard where the event's endpoints can be managed, if there is one
+    async fn dashboard_url(&self, event: &str, read_only: bool) -> Result<Option<String>, Error>;
+}
+
+/// A webhook client publishing messages to the configured sink
+#[derive(Clone)]
+pub struct Client {
+    sink: Arc<dyn WebhookSink>,
+}
+
+impl Client {
+    /// Create a new client publishing messages to the sink
+    pub fn new<S: WebhookSink + 'static>(sink: S) -> Self {
+        Self {
+            sink: Arc::new(sink),
+        }
+    }
+
+    /// Publish a queued webhook to all the event's endpoints
+    pub async fn publish(&self, webhook: &OutboxWebhook, db: &PgPool) -> Result<(), Error> {
+        self.sink.publish(webhook, db).await
+    }
+
+    /// Get a link to a dashboard where the event's endpoints can be managed, if there is one
+    pub async fn dashboard_url(
+        &self,
+        event: &str,
+        read_only: bool,
+    ) -> Result<Option<String>, Error> {
+        self.sink.dashboard_url(event, read_only).await
+    }
+}
+
+/// Queue a webhook event to be published once the transaction commits
+#[instrument(name = "webhooks::enqueue", skip(conn, object))]
+pub async fn enqueue<T>(
+    conn: &mut PgConnection,
+    event_type: &str,
+    event_slug: &str,
+    object: &T,
+) -> Result<(), database::Error>
 where
     T: Serialize,
 {
-    let body = serde_json::to_value(Payload {
+    let payload = serde_json::to_value(Payload {
         type_: event_type,
         for_: event_slug,
         object,
@@ -36,19 +128,46 @@ where
     })
     .expect("must serialize");
 
-    let client = client.clone();
-    let task_event_slug = event_slug.to_owned();
-    let task_event_type = event_type.to_owned();
-    tokio::task::spawn(
-        async move {
-            let result = client
-                .message()
-                .create(task_event_slug, MessageIn::new(task_event_type, body), None)
-                .await;
-            if let Err(error) = result {
-                error!(%error, "failed to send webhook");
+    OutboxWebhook::enqueue(event_slug, event_type, &payload, conn).await
+}
+
+/// Publish a batch of the queued webhooks that are due, returning how many were attempted
+///
+/// Failed attempts are retried with exponential backoff until the maximum number of attempts is
+/// reached, after which the webhook is marked as failed. Each outcome is saved as soon as it is
+/// known, so no locks are held while publishing and a later failure cannot undo the record of a
+/// webhook that was already published.
+#[instrument(name = "webhooks::publish", skip(client, db))]
+pub async fn publish(
+    client: &Client,
+    db: &PgPool,
+    batch_size: i64,
+    max_attempts: i32,
+) -> Result<usize, database::Error> {
+    let claimed_until = Utc::now() + Duration::seconds(CLAIM_SECONDS);
+    let webhooks = OutboxWebhook::claim_due(batch_size, claimed_until, db).await?;
+
+    for webhook in &webhooks {
+        match client.publish(webhook, db).await {
+            Ok(_) => OutboxWebhook::mark_sent(webhook.id, db).await?,
+            Err(error) => {
+                let attempts = webhook.attempts + 1;
+                let retry_at = (attempts < max_attempts).then(|| Utc::now() + backoff(attempts));
+                warn!(%error, id = %webhook.id, attempts, "failed to publish webhook");
+
+                OutboxWebhook::mark_attempt_failed(webhook.id, &error.to_string(), retry_at, db)
+                    .await?;
             }
         }
-        .instrument(info_span!("webhook::send::task", kind = %event_type, slug = %event_slug)),
-    );
+    }
+
+    Ok(webhooks.len())
+}
+
+/// Get the delay before the next publishing attempt
+fn backoff(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    let seconds = BASE_DELAY_SECONDS.saturating_mul(2i64.pow(exponent));
+
+    Duration::seconds(seconds.min(MAX_DELAY_SECONDS))
 }
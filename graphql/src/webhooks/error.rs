@@ -0,0 +1,44 @@
+use std::fmt::{Display, Formatter};
+
+/// The different ways publishing a webhook can fail
+#[derive(Debug)]
+pub enum Error {
+    /// The request to Svix failed
+    Svix(svix::error::Error),
+    /// The endpoints could not be loaded or updated
+    Database(database::Error),
+    /// The webhook could not be delivered to some of the endpoints
+    Delivery(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Svix(e) => write!(f, "svix request failed: {e}"),
+            Self::Database(e) => write!(f, "database error: {e}"),
+            Self::Delivery(e) => write!(f, "delivery failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Svix(e) => Some(e),
+            Self::Database(e) => Some(e),
+            Self::Delivery(_) => None,
+        }
+    }
+}
+
+impl From<svix::error::Error> for Error {
+    fn from(error: svix::error::Error) -> Self {
+        Self::Svix(error)
+    }
+}
+
+impl From<database::Error> for Error {
+    fn from(error: database::Error) -> Self {
+        Self::Database(error)
+    }
+}
@@ -0,0 +1,223 @@
+use super::{Error, WebhookSink};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use database::{OutboxWebhook, PgPool, WebhookEndpoint};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect::Policy,
+    Url,
+};
+use sha2::Sha256;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{instrument, warn};
+
+/// The prefix added to every endpoint's signing secret
+const SECRET_PREFIX: &str = "whsec_";
+/// How long to wait for an endpoint to respond
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Deliver webhooks directly to the endpoints stored for each event
+///
+/// Payloads are signed the same way as Svix, so consumers can verify them using any of the Svix
+/// libraries. A webhook is only marked as delivered to an endpoint once the endpoint responds
+/// successfully, so retries skip the endpoints that already received it.
+///
+/// Endpoints can only be reached at public addresses, so organizers cannot use them to make
+/// requests into the private network the portal runs in.
+pub struct HttpSink {
+    client: reqwest::Client,
+}
+
+impl HttpSink {
+    /// Create a new dispatcher that loads endpoints from the database
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent("the-hacker-app/portal")
+            .timeout(TIMEOUT)
+            .redirect(Policy::none())
+            .dns_resolver(Arc::new(PublicResolver))
+            .build()
+            .expect("client must build");
+
+        Self { client }
+    }
+
+    /// Deliver a webhook to a single endpoint
+    #[instrument(name = "HttpSink::deliver", skip_all, fields(endpoint = %endpoint.id))]
+    async fn deliver(
+        &self,
+        endpoint: &WebhookEndpoint,
+        id: &str,
+        body: &str,
+    ) -> Result<(), String> {
+        let url = Url::parse(&endpoint.url).map_err(|error| error.to_string())?;
+        check_url(&url).await?;
+
+        let timestamp = Utc::now().timestamp();
+        let signature = sign(&endpoint.secret, id, timestamp, body)?;
+
+        self.client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("webhook-id", id)
+            .header("webhook-timestamp", timestamp.to_string())
+            .header("webhook-signature", signature)
+            .body(body.to_owned())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|error| error.to_string())?;
+
+        Ok(())
+    }
+}
+
+impl Default for HttpSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WebhookSink for HttpSink {
+    #[instrument(name = "HttpSink::publish", skip_all, fields(id = %webhook.id))]
+    async fn publish(&self, webhook: &OutboxWebhook, db: &PgPool) -> Result<(), Error> {
+        let endpoints =
+            WebhookEndpoint::undelivered(&webhook.event, &webhook.event_type, webhook.id, db)
+                .await?;
+
+        let id = webhook.id.to_string();
+        let body = webhook.payload.to_string();
+
+        let mut failures = Vec::new();
+        for endpoint in &endpoints {
+            match self.deliver(endpoint, &id, &body).await {
+                Ok(()) => WebhookEndpoint::mark_delivered(endpoint.id, webhook.id, db).await?,
+                Err(error) => {
+                    warn!(endpoint = %endpoint.id, %error, "failed to deliver webhook to endpoint");
+                    failures.push(format!("{}: {error}", endpoint.url));
+                }
+            }
+        }
+
+        match failures.is_empty() {
+            true => Ok(()),
+            false => Err(Error::Delivery(failures.join("; "))),
+        }
+    }
+
+    async fn dashboard_url(&self, _event: &str, _read_only: bool) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+}
+
+/// Resolves endpoint hosts, refusing to connect to any that point inside the private network
+///
+/// Resolving again for every connection means a host cannot pass [`check_url`] and then switch
+/// to a private address before the request is made.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = resolve_public(name.as_str(), 0).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Check that webhooks can be delivered to the URL, rejecting hosts with private addresses
+pub(crate) async fn check_url(url: &Url) -> Result<(), String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(String::from("must be an http(s) url"));
+    }
+
+    let host = url.host_str().ok_or("must have a host")?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = url.port_or_known_default().unwrap_or_default();
+    resolve_public(host, port).await?;
+
+    Ok(())
+}
+
+/// Resolve a host, failing if any of its addresses are not publicly routable
+async fn resolve_public(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    let addrs = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|_| format!("could not resolve {host}"))?
+        .collect::<Vec<_>>();
+
+    if addrs.is_empty() {
+        return Err(format!("could not resolve {host}"));
+    }
+    if addrs.iter().any(|addr| !is_public(addr.ip())) {
+        return Err(format!("{host} must not resolve to a private address"));
+    }
+
+    Ok(addrs)
+}
+
+/// Whether an address is publicly routable
+///
+/// Loopback, private, link-local (including cloud metadata services), shared, unspecified and
+/// multicast addresses are all rejected.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            let shared = first == 100 && (second & 0xc0) == 64;
+
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || shared)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// Generate a new random secret for signing webhooks
+pub(crate) fn generate_secret() -> String {
+    let mut key = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut key);
+
+    format!("{SECRET_PREFIX}{}", STANDARD.encode(key))
+}
+
+/// Sign a webhook payload using the endpoint's secret
+///
+/// The signature is an HMAC-SHA256 over `{id}.{timestamp}.{body}`, formatted as Svix does.
+fn sign(secret: &str, id: &str, timestamp: i64, body: &str) -> Result<String, String> {
+    let key = secret.strip_prefix(SECRET_PREFIX).unwrap_or(secret);
+    let key = STANDARD
+        .decode(key)
+        .map_err(|_| String::from("endpoint has an invalid secret"))?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(&key).expect("hmac accepts keys of any length");
+    mac.update(format!("{id}.{timestamp}.{body}").as_bytes());
+
+    Ok(format!(
+        "v1,{}",
+        STANDARD.encode(mac.finalize().into_bytes())
+    ))
+}
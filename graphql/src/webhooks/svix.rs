@@ -0,0 +1,60 @@
+use super::{Error, WebhookSink};
+use async_trait::async_trait;
+use database::{OutboxWebhook, PgPool};
+use svix::api::{AppPortalAccessIn, MessageIn, PostOptions, Svix};
+use tracing::instrument;
+
+/// When the webhook dashboard should expire (1 day in seconds)
+const DASHBOARD_EXPIRY: i32 = 60 * 60 * 24;
+
+/// Publish webhooks through Svix, which handles the endpoints, signing and retries
+pub struct SvixSink {
+    client: Svix,
+}
+
+impl SvixSink {
+    /// Create a new sink using a Svix environment API key
+    pub fn new(api_key: String) -> Self {
+        Self {
+            client: Svix::new(api_key, None),
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookSink for SvixSink {
+    /// Publish the webhook, using its outbox ID as the idempotency key so that any duplicates from
+    /// retrying are suppressed
+    #[instrument(name = "SvixSink::publish", skip_all, fields(id = %webhook.id))]
+    async fn publish(&self, webhook: &OutboxWebhook, _db: &PgPool) -> Result<(), Error> {
+        let mut message = MessageIn::new(webhook.event_type.clone(), webhook.payload.clone());
+        message.event_id = Some(webhook.id.to_string());
+        let options = PostOptions {
+            idempotency_key: Some(webhook.id.to_string()),
+        };
+
+        self.client
+            .message()
+            .create(webhook.event.clone(), message, Some(options))
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "SvixSink::dashboard_url", skip(self))]
+    async fn dashboard_url(&self, event: &str, read_only: bool) -> Result<Option<String>, Error> {
+        let options = AppPortalAccessIn {
+            expiry: Some(DASHBOARD_EXPIRY),
+            read_only: Some(read_only),
+            ..AppPortalAccessIn::default()
+        };
+
+        let dashboard = self
+            .client
+            .authentication()
+            .app_portal_access(event.to_owned(), options, None)
+            .await?;
+
+        Ok(Some(dashboard.url))
+    }
+}
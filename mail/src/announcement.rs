@@ -0,0 +1,72 @@
+use crate::{AnnouncementModel, Client, Template, MAX_BATCH_SIZE};
+use database::{Announcement, AnnouncementRecipient, EmailSettings, PgPool};
+use tracing::{instrument, warn};
+
+/// Deliver a batch of the announcements waiting to be sent, returning how many recipients were
+/// attempted
+///
+/// Each announcement is sent using the batch endpoint, recording whether it was delivered to each
+/// recipient. Every chunk of recipients is recorded in its own transaction as soon as it is sent,
+/// so a failure later in the batch never causes them to receive the announcement again.
+/// Announcements are not retried if they could not be delivered.
+#[instrument(name = "announcement::deliver", skip_all)]
+pub async fn deliver(client: &Client, db: &PgPool) -> Result<usize, database::Error> {
+    let mut attempted = 0;
+    while attempted < MAX_BATCH_SIZE {
+        let mut txn = db.begin().await?;
+
+        let limit = (MAX_BATCH_SIZE - attempted) as i64;
+        let recipients = AnnouncementRecipient::pending(limit, &mut txn).await?;
+        let Some(first) = recipients.first() else {
+            break;
+        };
+
+        // only the first announcement is sent, any other recipients are unlocked once committed
+        let count = recipients
+            .iter()
+            .take_while(|recipient| recipient.announcement_id == first.announcement_id)
+            .count();
+        let recipients = &recipients[..count];
+
+        let event = &recipients[0].event;
+        let outcomes =
+            match Announcement::find(event, recipients[0].announcement_id, &mut txn).await? {
+                Some(announcement) => {
+                    let settings = EmailSettings::find(event, &mut txn).await?;
+                    let template = Template::Announcement(AnnouncementModel {
+                        event: announcement.event,
+                        subject: announcement.subject,
+                        body: announcement.body,
+                    });
+                    let addresses = recipients
+                        .iter()
+                        .map(|recipient| recipient.address.as_str())
+                        .collect::<Vec<_>>();
+
+                    match client.send_batch(&template, &addresses, &settings).await {
+                        Ok(outcomes) => outcomes,
+                        Err(error) => {
+                            warn!(%error, id = %announcement.id, "failed to deliver announcement");
+                            vec![Err(error.to_string()); recipients.len()]
+                        }
+                    }
+                }
+                None => vec![Err(String::from("announcement not found")); recipients.len()],
+            };
+
+        for (recipient, outcome) in recipients.iter().zip(outcomes) {
+            AnnouncementRecipient::mark(
+                recipient.announcement_id,
+                recipient.participant_id,
+                outcome.err().as_deref(),
+                &mut txn,
+            )
+            .await?;
+        }
+
+        txn.commit().await?;
+        attempted += recipients.len();
+    }
+
+    Ok(attempted)
+}
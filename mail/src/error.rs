@@ -0,0 +1,80 @@
+use std::fmt::{Display, Formatter};
+
+/// The different ways sending an email can fail
+#[derive(Debug)]
+pub enum Error {
+    /// The request to Postmark failed
+    Postmark(reqwest::Error),
+    /// The message could not be delivered to the SMTP server
+    Smtp(lettre::transport::smtp::Error),
+    /// One of the message's addresses is invalid
+    Address(lettre::address::AddressError),
+    /// The message could not be built
+    Message(lettre::error::Error),
+    /// The message could not be serialized
+    Serialize(serde_json::Error),
+    /// The message could not be written to disk
+    Io(std::io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Postmark(e) => write!(f, "postmark request failed: {e}"),
+            Self::Smtp(e) => write!(f, "smtp delivery failed: {e}"),
+            Self::Address(e) => write!(f, "invalid address: {e}"),
+            Self::Message(e) => write!(f, "invalid message: {e}"),
+            Self::Serialize(e) => write!(f, "failed to serialize message: {e}"),
+            Self::Io(e) => write!(f, "failed to write message: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Postmark(e) => Some(e),
+            Self::Smtp(e) => Some(e),
+            Self::Address(e) => Some(e),
+            Self::Message(e) => Some(e),
+            Self::Serialize(e) => Some(e),
+            Self::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Self::Postmark(error)
+    }
+}
+
+impl From<lettre::transport::smtp::Error> for Error {
+    fn from(error: lettre::transport::smtp::Error) -> Self {
+        Self::Smtp(error)
+    }
+}
+
+impl From<lettre::address::AddressError> for Error {
+    fn from(error: lettre::address::AddressError) -> Self {
+        Self::Address(error)
+    }
+}
+
+impl From<lettre::error::Error> for Error {
+    fn from(error: lettre::error::Error) -> Self {
+        Self::Message(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialize(error)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
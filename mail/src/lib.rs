@@ -1,65 +1,278 @@
-use reqwest::header::{HeaderMap, HeaderValue};
+use database::EmailSettings;
 use serde::Serialize;
-use std::collections::HashMap;
+use serde_json::Value;
+use std::sync::Arc;
 use tracing::instrument;
 
+pub mod announcement;
+mod error;
+pub mod outbox;
+mod template;
+mod transport;
+
+pub use error::Error;
+pub use template::{AcceptedModel, AnnouncementModel, ApplicationModel, Template};
+pub use transport::{Local, Postmark, Smtp, Transport};
+
+/// The address emails are sent from, unless overridden by the event
+const FROM: &str = "apply@wafflehacks.org";
+/// The address replies are sent to, unless overridden by the event
+const REPLY_TO: &str = "operations@wafflehacks.org";
+/// The Postmark message stream to send through, unless overridden by the event
+const MESSAGE_STREAM: &str = "outbound";
+
+/// The most addresses that can be sent to in a single batch
+pub const MAX_BATCH_SIZE: usize = 500;
+
 /// An email client for sending templated messages to one or more participants
 #[derive(Clone)]
 pub struct Client {
-    client: reqwest::Client,
+    transport: Arc<dyn Transport>,
 }
 
 impl Client {
-    /// Create a new client
-    pub fn new(token: String) -> Self {
-        let client = reqwest::Client::builder()
-            .user_agent("the-hacker-app/portal")
-            .danger_accept_invalid_certs(true)
-            .default_headers({
-                let mut headers = HeaderMap::with_capacity(1);
-                headers.insert("Accept", HeaderValue::from_static("application/json"));
-                headers.insert(
-                    "X-Postmark-Server-Token",
-                    HeaderValue::try_from(token).expect("token must be valid"),
-                );
-                headers
-            })
-            .build()
-            .expect("client must build");
+    /// Create a new client delivering messages with the transport
+    pub fn new<T: Transport + 'static>(transport: T) -> Self {
+        Self {
+            transport: Arc::new(transport),
+        }
+    }
+
+    /// Send a templated email to many addresses at once, as configured by the event's settings
+    ///
+    /// Returns whether the email was delivered to each address, in the same order as the
+    /// addresses. The addresses are sent to in chunks of at most [`MAX_BATCH_SIZE`].
+    #[instrument(skip_all, fields(template = template.alias(), event = settings.event, count = to.len()))]
+    pub async fn send_batch(
+        &self,
+        template: &Template,
+        to: &[&str],
+        settings: &EmailSettings,
+    ) -> Result<Vec<Result<(), String>>, Error> {
+        let model = template.model();
+
+        let mut outcomes = Vec::with_capacity(to.len());
+        for chunk in to.chunks(MAX_BATCH_SIZE) {
+            let messages = chunk
+                .iter()
+                .map(|to| Message {
+                    template: settings.alias_for(template.alias()),
+                    model: &model,
+                    to,
+                    from: settings.from_address.as_deref().unwrap_or(FROM),
+                    reply_to: settings.reply_to.as_deref().unwrap_or(REPLY_TO),
+                    message_stream: settings.message_stream.as_deref().unwrap_or(MESSAGE_STREAM),
+                })
+                .collect::<Vec<_>>();
 
-        Self { client }
+            outcomes.extend(self.transport.send_batch(&messages).await?);
+        }
+
+        Ok(outcomes)
     }
 
-    /// Send a templated email to the specified address
-    #[instrument(skip(self))]
-    pub async fn send_templated(&self, id: &str, to: &str) -> Result<(), reqwest::Error> {
-        self.client
-            .post("https://api.postmarkapp.com/email/withTemplate")
-            .json(&SendTemplateRequest {
-                template_alias: id,
-                template_model: HashMap::with_capacity(0),
+    /// Send a templated email to the specified address, as configured by the event's settings
+    #[instrument(skip_all, fields(template = template.alias(), event = settings.event))]
+    pub async fn send_templated(
+        &self,
+        template: &Template,
+        to: &str,
+        settings: &EmailSettings,
+    ) -> Result<(), Error> {
+        self.transport
+            .send(&Message {
+                template: settings.alias_for(template.alias()),
+                model: &template.model(),
                 to,
-                from: "apply@wafflehacks.org",
-                reply_to: "operations@wafflehacks.org",
-                track_opens: true,
-                message_stream: "outbound",
+                from: settings.from_address.as_deref().unwrap_or(FROM),
+                reply_to: settings.reply_to.as_deref().unwrap_or(REPLY_TO),
+                message_stream: settings.message_stream.as_deref().unwrap_or(MESSAGE_STREAM),
             })
-            .send()
-            .await?
-            .error_for_status()?;
-
-        Ok(())
+            .await
     }
 }
 
-#[derive(Serialize)]
-#[serde(rename_all = "PascalCase")]
-struct SendTemplateRequest<'a> {
-    template_alias: &'a str,
-    template_model: HashMap<String, String>,
-    to: &'a str,
-    from: &'static str,
-    reply_to: &'static str,
-    track_opens: bool,
-    message_stream: &'static str,
+/// A templated email to be delivered
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message<'a> {
+    /// The alias of the template to render
+    pub template: &'a str,
+    /// The variables to render the template with
+    pub model: &'a Value,
+    /// The recipient's address
+    pub to: &'a str,
+    /// The sender's address
+    pub from: &'a str,
+    /// The address replies should be sent to
+    pub reply_to: &'a str,
+    /// The stream the message belongs to
+    pub message_stream: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use sqlx::types::Json;
+    use std::{collections::HashMap, sync::Mutex};
+
+    /// The parts of a message that were sent
+    #[derive(Debug, PartialEq)]
+    struct Sent {
+        template: String,
+        to: String,
+        from: String,
+        reply_to: String,
+        message_stream: String,
+        model: Value,
+    }
+
+    /// Keep track of the messages that were sent
+    #[derive(Clone, Default)]
+    struct Recording(Arc<Mutex<Vec<Sent>>>);
+
+    #[async_trait]
+    impl Transport for Recording {
+        async fn send(&self, message: &Message<'_>) -> Result<(), Error> {
+            self.0.lock().unwrap().push(Sent {
+                template: message.template.to_owned(),
+                to: message.to.to_owned(),
+                from: message.from.to_owned(),
+                reply_to: message.reply_to.to_owned(),
+                message_stream: message.message_stream.to_owned(),
+                model: message.model.clone(),
+            });
+            Ok(())
+        }
+    }
+
+    fn settings() -> EmailSettings {
+        EmailSettings {
+            event: String::from("test"),
+            from_address: None,
+            reply_to: None,
+            message_stream: None,
+            template_aliases: Json(HashMap::new()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn template() -> Template {
+        Template::Pending(ApplicationModel {
+            event: String::from("test"),
+            event_name: String::from("Test Hacks"),
+            participant_id: 1,
+            first_name: Some(String::from("Ada")),
+            last_name: Some(String::from("Lovelace")),
+        })
+    }
+
+    #[tokio::test]
+    async fn send_templated_uses_defaults() {
+        let transport = Recording::default();
+        let client = Client::new(transport.clone());
+
+        client
+            .send_templated(&template(), "hacker@example.com", &settings())
+            .await
+            .unwrap();
+
+        let sent = transport.0.lock().unwrap();
+        assert_eq!(
+            *sent,
+            [Sent {
+                template: String::from("pending"),
+                to: String::from("hacker@example.com"),
+                from: String::from(FROM),
+                reply_to: String::from(REPLY_TO),
+                message_stream: String::from(MESSAGE_STREAM),
+                model: template().model(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn send_templated_uses_event_settings() {
+        let transport = Recording::default();
+        let client = Client::new(transport.clone());
+        let settings = EmailSettings {
+            from_address: Some(String::from("apply@example.com")),
+            reply_to: Some(String::from("help@example.com")),
+            message_stream: Some(String::from("event")),
+            template_aliases: Json(HashMap::from([(
+                String::from("pending"),
+                String::from("event-pending"),
+            )])),
+            ..settings()
+        };
+
+        client
+            .send_templated(&template(), "hacker@example.com", &settings)
+            .await
+            .unwrap();
+
+        let sent = transport.0.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].template, "event-pending");
+        assert_eq!(sent[0].from, "apply@example.com");
+        assert_eq!(sent[0].reply_to, "help@example.com");
+        assert_eq!(sent[0].message_stream, "event");
+    }
+
+    #[tokio::test]
+    async fn send_batch_delivers_to_each_address_in_order() {
+        let transport = Recording::default();
+        let client = Client::new(transport.clone());
+        let to = ["one@example.com", "two@example.com", "three@example.com"];
+
+        let outcomes = client
+            .send_batch(&template(), &to, &settings())
+            .await
+            .unwrap();
+
+        assert_eq!(outcomes, vec![Ok(()); 3]);
+        let sent = transport.0.lock().unwrap();
+        let addresses = sent.iter().map(|sent| sent.to.as_str()).collect::<Vec<_>>();
+        assert_eq!(addresses, to);
+    }
+
+    #[tokio::test]
+    async fn send_batch_chunks_large_batches() {
+        /// Count the batches sent through the transport
+        #[derive(Clone, Default)]
+        struct Batches(Arc<Mutex<Vec<usize>>>);
+
+        #[async_trait]
+        impl Transport for Batches {
+            async fn send(&self, _message: &Message<'_>) -> Result<(), Error> {
+                Ok(())
+            }
+
+            async fn send_batch(
+                &self,
+                messages: &[Message<'_>],
+            ) -> Result<Vec<Result<(), String>>, Error> {
+                self.0.lock().unwrap().push(messages.len());
+                Ok(vec![Ok(()); messages.len()])
+            }
+        }
+
+        let transport = Batches::default();
+        let client = Client::new(transport.clone());
+        let to = vec!["hacker@example.com"; MAX_BATCH_SIZE * 2 + 1];
+
+        let outcomes = client
+            .send_batch(&template(), &to, &settings())
+            .await
+            .unwrap();
+
+        assert_eq!(outcomes.len(), to.len());
+        assert_eq!(
+            *transport.0.lock().unwrap(),
+            [MAX_BATCH_SIZE, MAX_BATCH_SIZE, 1]
+        );
+    }
 }
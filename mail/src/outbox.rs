@@ -0,0 +1,76 @@
+use crate::{Client, Template};
+use chrono::{Duration, Utc};
+use database::{EmailSettings, OutboxEmail, PgPool};
+use std::collections::HashMap;
+use tracing::{instrument, warn};
+
+/// The delay before the first retry, doubled after each subsequent failure
+const BASE_DELAY_SECONDS: i64 = 30;
+/// The longest delay between retries
+const MAX_DELAY_SECONDS: i64 = 60 * 60;
+/// How long a batch is reserved for a worker before other workers can attempt it
+const CLAIM_SECONDS: i64 = 5 * 60;
+
+/// Attempt to deliver a batch of the queued emails that are due, returning how many were attempted
+///
+/// Failed deliveries are retried with exponential backoff until the maximum number of attempts is
+/// reached, after which the email is marked as failed. The batch is claimed up front and the
+/// outcome of each email is saved as soon as it is known, so a failure part way through never
+/// causes the emails that were already sent to be delivered again.
+#[instrument(name = "outbox::deliver", skip(client, db))]
+pub async fn deliver(
+    client: &Client,
+    db: &PgPool,
+    batch_size: i64,
+    max_attempts: i32,
+) -> Result<usize, database::Error> {
+    let claimed_until = Utc::now() + Duration::seconds(CLAIM_SECONDS);
+    let emails = OutboxEmail::claim_due(batch_size, claimed_until, db).await?;
+
+    let mut settings = HashMap::new();
+    for email in &emails {
+        if !settings.contains_key(&email.event) {
+            let found = EmailSettings::find(&email.event, db).await?;
+            settings.insert(email.event.clone(), found);
+        }
+    }
+
+    for email in &emails {
+        let template = match Template::from_parts(&email.template, email.model.clone()) {
+            Ok(template) => template,
+            Err(error) => {
+                warn!(%error, id = %email.id, "email has an invalid template");
+
+                let error = format!("invalid template: {error}");
+                OutboxEmail::mark_attempt_failed(email.id, &error, None, db).await?;
+                continue;
+            }
+        };
+
+        let settings = &settings[&email.event];
+        match client
+            .send_templated(&template, &email.recipient, settings)
+            .await
+        {
+            Ok(()) => OutboxEmail::mark_sent(email.id, db).await?,
+            Err(error) => {
+                let attempts = email.attempts + 1;
+                let retry_at = (attempts < max_attempts).then(|| Utc::now() + backoff(attempts));
+                warn!(%error, id = %email.id, attempts, "failed to deliver email");
+
+                OutboxEmail::mark_attempt_failed(email.id, &error.to_string(), retry_at, db)
+                    .await?;
+            }
+        }
+    }
+
+    Ok(emails.len())
+}
+
+/// Get the delay before the next delivery attempt
+fn backoff(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    let seconds = BASE_DELAY_SECONDS.saturating_mul(2i64.pow(exponent));
+
+    Duration::seconds(seconds.min(MAX_DELAY_SECONDS))
+}
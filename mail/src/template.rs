@@ -0,0 +1,208 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The templates that can be sent, along with the variables available to each of them
+///
+/// The variant names map to the template aliases configured in Postmark.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "alias", content = "model", rename_all = "kebab-case")]
+pub enum Template {
+    /// Sent once an application is submitted
+    ///
+    /// Variables: `event`, `event_name`, `participant_id`, `first_name`, `last_name`
+    Pending(ApplicationModel),
+    /// Sent when an application is placed on the waitlist
+    ///
+    /// Variables: `event`, `event_name`, `participant_id`, `first_name`, `last_name`
+    Waitlisted(ApplicationModel),
+    /// Sent when an application is rejected
+    ///
+    /// Variables: `event`, `event_name`, `participant_id`, `first_name`, `last_name`
+    Rejected(ApplicationModel),
+    /// Sent when an application is accepted, either directly or from the waitlist
+    ///
+    /// Variables: `event`, `event_name`, `participant_id`, `first_name`, `last_name`,
+    /// `rsvp_deadline`, `check_in_token`, `check_in_qr_code_url`
+    Accepted(AcceptedModel),
+    /// Sent when a participant confirms they will attend
+    ///
+    /// Variables: `event`, `event_name`, `participant_id`, `first_name`, `last_name`
+    Confirmed(ApplicationModel),
+    /// Sent when a participant declines to attend or misses their RSVP deadline
+    ///
+    /// Variables: `event`, `event_name`, `participant_id`, `first_name`, `last_name`
+    Declined(ApplicationModel),
+    /// Sent when a participant withdraws their application
+    ///
+    /// Variables: `event`, `event_name`, `participant_id`, `first_name`, `last_name`
+    Withdrawn(ApplicationModel),
+    /// Sent when organizers broadcast an announcement
+    ///
+    /// Variables: `event`, `subject`, `body`
+    Announcement(AnnouncementModel),
+}
+
+/// Define the alias of each template, along with the list of all the aliases
+macro_rules! aliases {
+    ( $( $variant:ident => $alias:literal ),+ $(,)? ) => {
+        impl Template {
+            /// The aliases of all the templates
+            pub const ALIASES: &'static [&'static str] = &[ $( $alias ),+ ];
+
+            /// The alias of the template in Postmark
+            pub fn alias(&self) -> &'static str {
+                match self {
+                    $( Self::$variant(_) => $alias, )+
+                }
+            }
+        }
+    };
+}
+
+aliases! {
+    Pending => "pending",
+    Waitlisted => "waitlisted",
+    Rejected => "rejected",
+    Accepted => "accepted",
+    Confirmed => "confirmed",
+    Declined => "declined",
+    Withdrawn => "withdrawn",
+    Announcement => "announcement",
+}
+
+impl Template {
+    /// Re-create a template from its alias and serialized model
+    pub fn from_parts(alias: &str, model: Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(serde_json::json!({ "alias": alias, "model": model }))
+    }
+
+    /// The variables to render the template with
+    pub fn model(&self) -> Value {
+        let model = match self {
+            Self::Pending(model)
+            | Self::Waitlisted(model)
+            | Self::Rejected(model)
+            | Self::Confirmed(model)
+            | Self::Declined(model)
+            | Self::Withdrawn(model) => serde_json::to_value(model),
+            Self::Accepted(model) => serde_json::to_value(model),
+            Self::Announcement(model) => serde_json::to_value(model),
+        };
+
+        model.expect("template models must serialize")
+    }
+}
+
+/// The variables shared by all the application-related templates
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApplicationModel {
+    /// The slug of the event the application is for
+    pub event: String,
+    /// The name of the event the application is for
+    pub event_name: String,
+    /// The ID of the participant who submitted the application
+    pub participant_id: i32,
+    /// The participant's first name, if known
+    pub first_name: Option<String>,
+    /// The participant's last name, if known
+    pub last_name: Option<String>,
+}
+
+/// The variables for the `accepted` template
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AcceptedModel {
+    /// The variables shared by all the application-related templates
+    #[serde(flatten)]
+    pub application: ApplicationModel,
+    /// When the participant must confirm their attendance by, if the event limits it
+    pub rsvp_deadline: Option<DateTime<Utc>>,
+    /// The signed token the participant checks in with
+    pub check_in_token: String,
+    /// A link to the check-in token as a PNG QR code, for use in an image tag
+    pub check_in_qr_code_url: String,
+}
+
+/// The variables for the `announcement` template
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AnnouncementModel {
+    /// The slug of the event the announcement is for
+    pub event: String,
+    /// The subject line of the announcement
+    pub subject: String,
+    /// The content of the announcement
+    pub body: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn application() -> ApplicationModel {
+        ApplicationModel {
+            event: String::from("test"),
+            event_name: String::from("Test Hacks"),
+            participant_id: 1,
+            first_name: Some(String::from("Ada")),
+            last_name: None,
+        }
+    }
+
+    #[test]
+    fn aliases_match_serialized_names() {
+        let templates = [
+            Template::Pending(application()),
+            Template::Waitlisted(application()),
+            Template::Rejected(application()),
+            Template::Accepted(AcceptedModel {
+                application: application(),
+                rsvp_deadline: None,
+                check_in_token: String::from("1.token"),
+                check_in_qr_code_url: String::from("https://portal.example.com/check-in-code"),
+            }),
+            Template::Confirmed(application()),
+            Template::Declined(application()),
+            Template::Withdrawn(application()),
+            Template::Announcement(AnnouncementModel {
+                event: String::from("test"),
+                subject: String::from("Hello"),
+                body: String::from("World"),
+            }),
+        ];
+        assert_eq!(templates.len(), Template::ALIASES.len());
+
+        for (template, alias) in templates.iter().zip(Template::ALIASES) {
+            assert_eq!(template.alias(), *alias);
+
+            let serialized = serde_json::to_value(template).unwrap();
+            assert_eq!(serialized["alias"], *alias);
+
+            let parsed = Template::from_parts(alias, template.model()).unwrap();
+            assert_eq!(parsed.alias(), *alias);
+        }
+    }
+
+    #[test]
+    fn accepted_model_is_flat() {
+        let template = Template::Accepted(AcceptedModel {
+            application: application(),
+            rsvp_deadline: None,
+            check_in_token: String::from("1.token"),
+            check_in_qr_code_url: String::from("https://portal.example.com/check-in-code"),
+        });
+
+        assert_eq!(
+            template.model(),
+            serde_json::json!({
+                "event": "test",
+                "event_name": "Test Hacks",
+                "participant_id": 1,
+                "first_name": "Ada",
+                "last_name": null,
+                "rsvp_deadline": null,
+                "check_in_token": "1.token",
+                "check_in_qr_code_url": "https://portal.example.com/check-in-code",
+            })
+        );
+    }
+}
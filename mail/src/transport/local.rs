@@ -0,0 +1,103 @@
+use super::Transport;
+use crate::{Error, Message};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::path::PathBuf;
+use tokio::fs;
+use tracing::{info, instrument};
+
+/// Log emails instead of delivering them, optionally writing them to a directory
+///
+/// Each message is written as a JSON file so it can be inspected during development and tests.
+pub struct Local {
+    directory: Option<PathBuf>,
+}
+
+impl Local {
+    /// Create a new local transport, writing messages to the directory if provided
+    pub fn new(directory: Option<PathBuf>) -> Self {
+        Self { directory }
+    }
+}
+
+#[async_trait]
+impl Transport for Local {
+    #[instrument(name = "Local::send", skip_all, fields(template = message.template))]
+    async fn send(&self, message: &Message<'_>) -> Result<(), Error> {
+        info!(to = message.to, from = message.from, "sending email");
+
+        if let Some(directory) = &self.directory {
+            fs::create_dir_all(directory).await?;
+
+            let now = Utc::now();
+            let name = format!(
+                "{}-{}.json",
+                now.format("%Y%m%dT%H%M%S%.9f"),
+                message.template
+            );
+            fs::write(directory.join(name), serde_json::to_vec_pretty(message)?).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn message(model: &Value) -> Message<'_> {
+        Message {
+            template: "pending",
+            model,
+            to: "hacker@example.com",
+            from: "apply@example.com",
+            reply_to: "help@example.com",
+            message_stream: "outbound",
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_messages_to_directory() {
+        let directory = tempfile::tempdir().unwrap();
+        let transport = Local::new(Some(directory.path().join("mail")));
+        let model = json!({ "event": "test", "participant_id": 1 });
+
+        transport.send(&message(&model)).await.unwrap();
+
+        let mut entries = std::fs::read_dir(directory.path().join("mail"))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let entry = entries.pop().unwrap();
+        let name = entry.file_name().into_string().unwrap();
+        assert!(
+            name.ends_with("-pending.json"),
+            "unexpected file name {name}"
+        );
+
+        let written: Value = serde_json::from_slice(&std::fs::read(entry.path()).unwrap()).unwrap();
+        assert_eq!(
+            written,
+            json!({
+                "template": "pending",
+                "model": model,
+                "to": "hacker@example.com",
+                "from": "apply@example.com",
+                "replyTo": "help@example.com",
+                "messageStream": "outbound",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn only_logs_without_directory() {
+        let transport = Local::new(None);
+        let model = json!({});
+
+        transport.send(&message(&model)).await.unwrap();
+    }
+}
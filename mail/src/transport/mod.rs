@@ -0,0 +1,31 @@
+use crate::{Error, Message};
+use async_trait::async_trait;
+
+mod local;
+mod postmark;
+mod smtp;
+
+pub use local::Local;
+pub use postmark::Postmark;
+pub use smtp::Smtp;
+
+/// A method of delivering emails
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Deliver a single templated message
+    async fn send(&self, message: &Message<'_>) -> Result<(), Error>;
+
+    /// Deliver many templated messages at once, returning whether each message was delivered
+    ///
+    /// The outcomes are in the same order as the messages. By default, each message is delivered
+    /// individually.
+    async fn send_batch(&self, messages: &[Message<'_>]) -> Result<Vec<Result<(), String>>, Error> {
+        let mut outcomes = Vec::with_capacity(messages.len());
+        for message in messages {
+            let outcome = self.send(message).await.map_err(|e| e.to_string());
+            outcomes.push(outcome);
+        }
+
+        Ok(outcomes)
+    }
+}
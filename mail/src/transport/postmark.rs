@@ -0,0 +1,164 @@
+use super::Transport;
+use crate::{Error, Message};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use tracing::instrument;
+
+/// How many templates to list from Postmark at once
+const TEMPLATE_PAGE_SIZE: usize = 500;
+
+/// Deliver emails using Postmark's templates
+pub struct Postmark {
+    client: reqwest::Client,
+}
+
+impl Postmark {
+    /// Create a new Postmark transport using a server-scoped token
+    pub fn new(token: String) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent("the-hacker-app/portal")
+            .danger_accept_invalid_certs(true)
+            .default_headers({
+                let mut headers = HeaderMap::with_capacity(1);
+                headers.insert("Accept", HeaderValue::from_static("application/json"));
+                headers.insert(
+                    "X-Postmark-Server-Token",
+                    HeaderValue::try_from(token).expect("token must be valid"),
+                );
+                headers
+            })
+            .build()
+            .expect("client must build");
+
+        Self { client }
+    }
+
+    /// Find which of the template aliases do not exist in the Postmark server
+    ///
+    /// This is checked on startup, since every email using a missing template would otherwise
+    /// fail until it runs out of attempts.
+    #[instrument(name = "Postmark::missing_templates", skip_all)]
+    pub async fn missing_templates<'a>(&self, aliases: &[&'a str]) -> Result<Vec<&'a str>, Error> {
+        let mut existing = HashSet::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .client
+                .get("https://api.postmarkapp.com/templates")
+                .query(&[("count", TEMPLATE_PAGE_SIZE), ("offset", offset)])
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<ListTemplatesResponse>()
+                .await?;
+
+            let fetched = page.templates.len();
+            offset += fetched;
+            existing.extend(page.templates.into_iter().filter_map(|t| t.alias));
+            if fetched < TEMPLATE_PAGE_SIZE || offset >= page.total_count {
+                break;
+            }
+        }
+
+        Ok(aliases
+            .iter()
+            .filter(|alias| !existing.contains(**alias))
+            .copied()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Transport for Postmark {
+    #[instrument(name = "Postmark::send", skip_all, fields(template = message.template))]
+    async fn send(&self, message: &Message<'_>) -> Result<(), Error> {
+        self.client
+            .post("https://api.postmarkapp.com/email/withTemplate")
+            .json(&SendTemplateRequest::from(message))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "Postmark::send_batch", skip_all, fields(count = messages.len()))]
+    async fn send_batch(&self, messages: &[Message<'_>]) -> Result<Vec<Result<(), String>>, Error> {
+        let responses = self
+            .client
+            .post("https://api.postmarkapp.com/email/batchWithTemplates")
+            .json(&SendBatchRequest {
+                messages: messages.iter().map(SendTemplateRequest::from).collect(),
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<SendResponse>>()
+            .await?;
+
+        let outcomes = responses
+            .into_iter()
+            .map(|response| match response.error_code {
+                0 => Ok(()),
+                code => Err(format!("{} (code {code})", response.message)),
+            })
+            .collect();
+
+        Ok(outcomes)
+    }
+}
+
+impl<'a> From<&'a Message<'a>> for SendTemplateRequest<'a> {
+    fn from(message: &'a Message<'a>) -> Self {
+        Self {
+            template_alias: message.template,
+            template_model: message.model,
+            to: message.to,
+            from: message.from,
+            reply_to: message.reply_to,
+            track_opens: true,
+            message_stream: message.message_stream,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct SendTemplateRequest<'a> {
+    template_alias: &'a str,
+    template_model: &'a Value,
+    to: &'a str,
+    from: &'a str,
+    reply_to: &'a str,
+    track_opens: bool,
+    message_stream: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct SendBatchRequest<'a> {
+    messages: Vec<SendTemplateRequest<'a>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SendResponse {
+    error_code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListTemplatesResponse {
+    total_count: usize,
+    templates: Vec<TemplateSummary>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TemplateSummary {
+    alias: Option<String>,
+}
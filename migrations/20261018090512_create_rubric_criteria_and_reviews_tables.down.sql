@@ -0,0 +1,3 @@
+DROP TABLE review_scores;
+DROP TABLE reviews;
+DROP TABLE rubric_criteria;
@@ -0,0 +1,47 @@
+CREATE TABLE rubric_criteria
+(
+    id          uuid primary key         not null default uuid_generate_v4(),
+    event       text                     not null,
+
+    name        text                     not null,
+    description text                     not null default '',
+    max_score   int                      not null check ( max_score > 0 ),
+
+    created_at  timestamp with time zone not null default now()
+);
+
+CREATE INDEX rubric_criteria_event_idx ON rubric_criteria (event);
+
+CREATE TABLE reviews
+(
+    event          text                     not null,
+    participant_id int                      not null,
+    reviewer_id    int                      not null,
+
+    comment        text                     not null default '',
+
+    created_at     timestamp with time zone not null default now(),
+    updated_at     timestamp with time zone not null default now(),
+
+    primary key (participant_id, event, reviewer_id),
+    foreign key (participant_id, event) references applications (participant_id, event) on delete cascade
+);
+
+CREATE TRIGGER set_reviews_updated_at_timestamp
+    BEFORE UPDATE
+    ON reviews
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at_timestamp();
+
+CREATE TABLE review_scores
+(
+    event          text not null,
+    participant_id int  not null,
+    reviewer_id    int  not null,
+    criterion_id   uuid not null references rubric_criteria (id) on delete cascade,
+
+    score          int  not null check ( score >= 0 ),
+
+    primary key (participant_id, event, reviewer_id, criterion_id),
+    foreign key (participant_id, event, reviewer_id) references reviews (participant_id, event, reviewer_id) on delete cascade
+);
@@ -0,0 +1,9 @@
+DROP INDEX applications_waitlist_idx;
+
+DROP TRIGGER set_applications_waitlisted_at_timestamp ON applications;
+DROP FUNCTION set_waitlisted_at_timestamp;
+
+ALTER TABLE applications
+    DROP COLUMN waitlisted_at;
+
+DROP TABLE event_settings;
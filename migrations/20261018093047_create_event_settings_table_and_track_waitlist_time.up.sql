@@ -0,0 +1,41 @@
+CREATE TABLE event_settings
+(
+    event      text primary key         not null,
+
+    capacity   int check ( capacity >= 0 ),
+
+    created_at timestamp with time zone not null default now(),
+    updated_at timestamp with time zone not null default now()
+);
+
+CREATE TRIGGER set_event_settings_updated_at_timestamp
+    BEFORE UPDATE
+    ON event_settings
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at_timestamp();
+
+ALTER TABLE applications
+    ADD COLUMN waitlisted_at timestamp with time zone;
+
+UPDATE applications
+SET waitlisted_at = updated_at
+WHERE status = 'waitlisted';
+
+CREATE FUNCTION set_waitlisted_at_timestamp()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    IF new.status = 'waitlisted' AND old.status IS DISTINCT FROM 'waitlisted' THEN
+        new.waitlisted_at = now();
+    END IF;
+    RETURN new;
+END;
+$$ LANGUAGE 'plpgsql';
+
+CREATE TRIGGER set_applications_waitlisted_at_timestamp
+    BEFORE UPDATE
+    ON applications
+    FOR EACH ROW
+EXECUTE PROCEDURE set_waitlisted_at_timestamp();
+
+CREATE INDEX applications_waitlist_idx ON applications (event, waitlisted_at) WHERE status = 'waitlisted';
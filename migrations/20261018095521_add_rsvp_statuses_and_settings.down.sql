@@ -0,0 +1,34 @@
+DROP TRIGGER set_applications_accepted_at_timestamp ON applications;
+DROP FUNCTION set_accepted_at_timestamp;
+
+ALTER TABLE applications
+    DROP COLUMN accepted_at;
+
+ALTER TABLE event_settings
+    DROP COLUMN rsvp_window_days,
+    DROP COLUMN check_in_requires_rsvp;
+
+-- map the removed statuses onto their closest equivalent
+UPDATE applications
+SET status = 'accepted'
+WHERE status = 'confirmed';
+UPDATE applications
+SET status = 'rejected'
+WHERE status = 'declined';
+
+-- rename the existing type so we can re-create it
+ALTER TYPE application_status RENAME TO old_application_status;
+
+-- re-create the type
+CREATE TYPE application_status AS ENUM ('pending', 'waitlisted', 'rejected', 'accepted');
+
+-- update the columns, the waitlist index depends on the type so it must be re-created
+DROP INDEX applications_waitlist_idx;
+ALTER TABLE applications
+    ALTER COLUMN status DROP DEFAULT,
+    ALTER COLUMN status TYPE application_status USING status::text::application_status,
+    ALTER COLUMN status SET DEFAULT 'pending';
+CREATE INDEX applications_waitlist_idx ON applications (event, waitlisted_at) WHERE status = 'waitlisted';
+
+-- delete the existing type
+DROP TYPE old_application_status;
@@ -0,0 +1,30 @@
+ALTER TYPE application_status ADD VALUE 'confirmed';
+ALTER TYPE application_status ADD VALUE 'declined';
+
+ALTER TABLE event_settings
+    ADD COLUMN rsvp_window_days          int check ( rsvp_window_days > 0 ),
+    ADD COLUMN check_in_requires_rsvp    bool not null default false;
+
+ALTER TABLE applications
+    ADD COLUMN accepted_at timestamp with time zone;
+
+UPDATE applications
+SET accepted_at = updated_at
+WHERE status = 'accepted';
+
+CREATE FUNCTION set_accepted_at_timestamp()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    IF new.status = 'accepted' AND old.status IS DISTINCT FROM 'accepted' THEN
+        new.accepted_at = now();
+    END IF;
+    RETURN new;
+END;
+$$ LANGUAGE 'plpgsql';
+
+CREATE TRIGGER set_applications_accepted_at_timestamp
+    BEFORE UPDATE
+    ON applications
+    FOR EACH ROW
+EXECUTE PROCEDURE set_accepted_at_timestamp();
@@ -0,0 +1,2 @@
+ALTER TABLE email_outbox
+    ADD COLUMN model jsonb not null default '{}';
@@ -0,0 +1,18 @@
+CREATE TABLE email_settings
+(
+    event            text primary key         not null,
+
+    from_address     text,
+    reply_to         text,
+    message_stream   text,
+    template_aliases jsonb                    not null default '{}',
+
+    created_at       timestamp with time zone not null default now(),
+    updated_at       timestamp with time zone not null default now()
+);
+
+CREATE TRIGGER set_email_settings_updated_at_timestamp
+    BEFORE UPDATE
+    ON email_settings
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at_timestamp();
@@ -0,0 +1,3 @@
+DROP TABLE announcement_recipients;
+DROP TYPE announcement_recipient_status;
+DROP TABLE announcements;
@@ -0,0 +1,30 @@
+CREATE TABLE announcements
+(
+    id         uuid primary key         not null default uuid_generate_v4(),
+    event      text                     not null,
+    sender_id  int                      not null,
+
+    subject    text                     not null,
+    body       text                     not null,
+
+    created_at timestamp with time zone not null default now()
+);
+
+CREATE INDEX announcements_event_idx ON announcements (event, created_at);
+
+CREATE TYPE announcement_recipient_status AS ENUM ('pending', 'sent', 'failed');
+
+CREATE TABLE announcement_recipients
+(
+    announcement_id uuid                          not null references announcements (id) on delete cascade,
+    participant_id  int                           not null,
+    address         text                          not null,
+
+    status          announcement_recipient_status not null default 'pending',
+    error           text,
+    sent_at         timestamp with time zone,
+
+    primary key (announcement_id, participant_id)
+);
+
+CREATE INDEX announcement_recipients_pending_idx ON announcement_recipients (announcement_id) WHERE status = 'pending';
@@ -0,0 +1,2 @@
+DROP TABLE webhook_outbox;
+DROP TYPE webhook_outbox_status;
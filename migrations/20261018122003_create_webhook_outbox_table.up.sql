@@ -0,0 +1,28 @@
+CREATE TYPE webhook_outbox_status AS ENUM ('pending', 'sent', 'failed');
+
+CREATE TABLE webhook_outbox
+(
+    id              uuid primary key         not null default uuid_generate_v4(),
+    event           text                     not null,
+
+    event_type      text                     not null,
+    payload         jsonb                    not null,
+
+    status          webhook_outbox_status    not null default 'pending',
+    attempts        int                      not null default 0,
+    last_error      text,
+    next_attempt_at timestamp with time zone not null default now(),
+    sent_at         timestamp with time zone,
+
+    created_at      timestamp with time zone not null default now(),
+    updated_at      timestamp with time zone not null default now()
+);
+
+CREATE INDEX webhook_outbox_event_status_idx ON webhook_outbox (event, status);
+CREATE INDEX webhook_outbox_due_idx ON webhook_outbox (next_attempt_at) WHERE status = 'pending';
+
+CREATE TRIGGER set_webhook_outbox_updated_at_timestamp
+    BEFORE UPDATE
+    ON webhook_outbox
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at_timestamp();
@@ -0,0 +1,29 @@
+CREATE TABLE webhook_endpoints
+(
+    id          uuid primary key         not null default uuid_generate_v4(),
+    event       text                     not null,
+
+    url         text                     not null,
+    secret      text                     not null,
+    event_types text[]                   not null default '{}',
+
+    created_at  timestamp with time zone not null default now(),
+    updated_at  timestamp with time zone not null default now()
+);
+
+CREATE INDEX webhook_endpoints_event_idx ON webhook_endpoints (event);
+
+CREATE TRIGGER set_webhook_endpoints_updated_at_timestamp
+    BEFORE UPDATE
+    ON webhook_endpoints
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at_timestamp();
+
+CREATE TABLE webhook_deliveries
+(
+    webhook_id   uuid                     not null references webhook_outbox (id) on delete cascade,
+    endpoint_id  uuid                     not null references webhook_endpoints (id) on delete cascade,
+    delivered_at timestamp with time zone not null default now(),
+
+    primary key (webhook_id, endpoint_id)
+);
@@ -0,0 +1,7 @@
+CREATE TABLE inbound_webhooks
+(
+    id          text primary key         not null,
+    received_at timestamp with time zone not null default now()
+);
+
+CREATE INDEX inbound_webhooks_received_at_idx ON inbound_webhooks (received_at);
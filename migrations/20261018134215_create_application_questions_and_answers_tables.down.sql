@@ -0,0 +1,5 @@
+DROP TABLE application_answers;
+DROP TABLE draft_application_answers;
+DROP TABLE application_questions;
+
+DROP TYPE application_question_kind;
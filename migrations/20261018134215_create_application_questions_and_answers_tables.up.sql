@@ -0,0 +1,52 @@
+CREATE TYPE application_question_kind AS ENUM ('short-text', 'long-text', 'single-choice', 'multiple-choice', 'number', 'boolean');
+
+CREATE TABLE application_questions
+(
+    id          uuid primary key          not null default uuid_generate_v4(),
+    event       text                      not null,
+
+    label       text                      not null,
+    description text                      not null default '',
+    kind        application_question_kind not null,
+    required    bool                      not null default false,
+    position    int                       not null default 0,
+
+    options     text[]                    not null default '{}',
+    minimum     int,
+    maximum     int,
+
+    created_at  timestamp with time zone  not null default now(),
+    updated_at  timestamp with time zone  not null default now()
+);
+
+CREATE INDEX application_questions_event_idx ON application_questions (event);
+
+CREATE TRIGGER set_application_questions_updated_at_timestamp
+    BEFORE UPDATE
+    ON application_questions
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at_timestamp();
+
+CREATE TABLE draft_application_answers
+(
+    event          text  not null,
+    participant_id int   not null,
+    question_id    uuid  not null references application_questions (id) on delete cascade,
+
+    value          jsonb not null,
+
+    primary key (participant_id, event, question_id),
+    foreign key (participant_id, event) references draft_applications (participant_id, event) on delete cascade
+);
+
+CREATE TABLE application_answers
+(
+    event          text  not null,
+    participant_id int   not null,
+    question_id    uuid  not null references application_questions (id) on delete cascade,
+
+    value          jsonb not null,
+
+    primary key (participant_id, event, question_id),
+    foreign key (participant_id, event) references applications (participant_id, event) on delete cascade
+);
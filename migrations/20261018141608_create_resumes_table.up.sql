@@ -0,0 +1,21 @@
+CREATE TABLE resumes
+(
+    event          text                     not null,
+    participant_id int                      not null,
+
+    key            text                     not null,
+    filename       text                     not null,
+    content_type   text                     not null,
+    size           int                      not null,
+
+    created_at     timestamp with time zone not null default now(),
+    updated_at     timestamp with time zone not null default now(),
+
+    primary key (participant_id, event)
+);
+
+CREATE TRIGGER set_resumes_updated_at_timestamp
+    BEFORE UPDATE
+    ON resumes
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at_timestamp();
@@ -0,0 +1,27 @@
+DROP TABLE application_audit_log;
+DROP TYPE application_audit_action;
+
+ALTER TABLE event_settings
+    DROP COLUMN application_edit_deadline;
+
+-- map the removed status onto its closest equivalent
+UPDATE applications
+SET status = 'declined'
+WHERE status = 'withdrawn';
+
+-- rename the existing type so we can re-create it
+ALTER TYPE application_status RENAME TO old_application_status;
+
+-- re-create the type
+CREATE TYPE application_status AS ENUM ('pending', 'waitlisted', 'rejected', 'accepted', 'confirmed', 'declined');
+
+-- update the columns, the waitlist index depends on the type so it must be re-created
+DROP INDEX applications_waitlist_idx;
+ALTER TABLE applications
+    ALTER COLUMN status DROP DEFAULT,
+    ALTER COLUMN status TYPE application_status USING status::text::application_status,
+    ALTER COLUMN status SET DEFAULT 'pending';
+CREATE INDEX applications_waitlist_idx ON applications (event, waitlisted_at) WHERE status = 'waitlisted';
+
+-- delete the existing type
+DROP TYPE old_application_status;
@@ -0,0 +1,23 @@
+ALTER TYPE application_status ADD VALUE 'withdrawn';
+
+ALTER TABLE event_settings
+    ADD COLUMN application_edit_deadline timestamp with time zone;
+
+CREATE TYPE application_audit_action AS ENUM ('updated', 'withdrawn');
+
+CREATE TABLE application_audit_log
+(
+    id             uuid primary key         not null default uuid_generate_v4(),
+    event          text                     not null,
+    participant_id int                      not null,
+
+    actor_id       int                      not null,
+    action         application_audit_action not null,
+    changes        jsonb                    not null default '{}',
+
+    created_at     timestamp with time zone not null default now(),
+
+    foreign key (participant_id, event) references applications (participant_id, event) on delete cascade
+);
+
+CREATE INDEX application_audit_log_application_idx ON application_audit_log (participant_id, event, created_at);
@@ -0,0 +1,2 @@
+DROP TABLE checkpoint_scans;
+DROP TABLE checkpoints;
@@ -0,0 +1,36 @@
+CREATE TABLE checkpoints
+(
+    id         uuid primary key         not null default uuid_generate_v4(),
+    event      text                     not null,
+
+    slug       text                     not null,
+    name       text                     not null,
+    capacity   int check ( capacity > 0 ),
+    once_only  bool                     not null default false,
+
+    created_at timestamp with time zone not null default now(),
+    updated_at timestamp with time zone not null default now(),
+
+    unique (event, slug)
+);
+
+CREATE TRIGGER set_checkpoints_updated_at_timestamp
+    BEFORE UPDATE
+    ON checkpoints
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at_timestamp();
+
+CREATE TABLE checkpoint_scans
+(
+    id             uuid primary key         not null default uuid_generate_v4(),
+    event          text                     not null,
+    checkpoint_id  uuid                     not null references checkpoints (id) on delete cascade,
+    participant_id int                      not null,
+
+    scanner_id     int                      not null,
+
+    at             timestamp with time zone not null default now()
+);
+
+CREATE INDEX checkpoint_scans_checkpoint_idx ON checkpoint_scans (checkpoint_id, participant_id);
+CREATE INDEX checkpoint_scans_participant_idx ON checkpoint_scans (participant_id, event);
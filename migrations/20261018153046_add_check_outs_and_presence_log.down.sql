@@ -0,0 +1,5 @@
+DROP TABLE presence_log;
+DROP TYPE presence_direction;
+
+ALTER TABLE check_ins
+    DROP COLUMN checked_out_at;
@@ -0,0 +1,22 @@
+ALTER TABLE check_ins
+    ADD COLUMN checked_out_at timestamp with time zone;
+
+CREATE TYPE presence_direction AS ENUM ('in', 'out');
+
+CREATE TABLE presence_log
+(
+    id             uuid primary key         not null default uuid_generate_v4(),
+    event          text                     not null,
+    participant_id int                      not null,
+
+    direction      presence_direction       not null,
+    actor_id       int,
+
+    at             timestamp with time zone not null default now()
+);
+
+CREATE INDEX presence_log_participant_idx ON presence_log (participant_id, event, at);
+
+INSERT INTO presence_log (event, participant_id, direction, at)
+SELECT event, participant_id, 'in', at
+FROM check_ins;
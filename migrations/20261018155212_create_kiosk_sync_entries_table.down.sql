@@ -0,0 +1 @@
+DROP TABLE kiosk_sync_entries;
@@ -0,0 +1,13 @@
+CREATE TABLE kiosk_sync_entries
+(
+    event            text                     not null,
+    participant_id   int                      not null,
+    checkpoint       text                     not null,
+    client_timestamp timestamp with time zone not null,
+
+    scanner_id       int                      not null,
+
+    synced_at        timestamp with time zone not null default now(),
+
+    primary key (participant_id, event, checkpoint, client_timestamp)
+);
@@ -0,0 +1,6 @@
+ALTER TABLE event_settings
+    DROP COLUMN name;
+
+ALTER TABLE emails
+    DROP COLUMN first_name,
+    DROP COLUMN last_name;
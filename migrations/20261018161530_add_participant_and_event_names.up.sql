@@ -0,0 +1,7 @@
+-- filled in by the next participant.updated webhook from the identity service
+ALTER TABLE emails
+    ADD COLUMN first_name text,
+    ADD COLUMN last_name  text;
+
+ALTER TABLE event_settings
+    ADD COLUMN name text;
@@ -1,23 +1,51 @@
-use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
-use axum::{extract::State, http::StatusCode, response::Html};
+use async_graphql::{
+    http::{playground_source, receive_body, GraphQLPlaygroundConfig},
+    ParseRequestError,
+};
+use async_graphql_axum::GraphQLResponse;
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+    response::Html,
+};
 use context::{Scope, User};
 use tracing::instrument;
 
+mod check_in;
+mod resumes;
 mod webhooks;
 
-pub use webhooks::router as webhooks;
+pub(crate) use check_in::code as check_in_code;
+pub(crate) use resumes::book as resume_book;
+pub use webhooks::{router as webhooks, Verifier};
 
 /// Handle graphql requests
+///
+/// The request is parsed here rather than with `GraphQLRequest` so that uploads are held to the
+/// same limits as the resumes they carry.
 #[instrument(name = "graphql", skip_all)]
 pub(crate) async fn graphql(
     State(schema): State<graphql::Schema>,
     scope: Scope,
     user: User,
-    req: GraphQLRequest,
-) -> GraphQLResponse {
-    let req = req.into_inner().data(scope).data(user);
-    schema.execute(req).await.into()
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<GraphQLResponse, (StatusCode, String)> {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    let req = receive_body(content_type, body.as_ref(), graphql::multipart_options())
+        .await
+        .map_err(|error| match error {
+            ParseRequestError::PayloadTooLarge => {
+                (StatusCode::PAYLOAD_TOO_LARGE, error.to_string())
+            }
+            error => (StatusCode::BAD_REQUEST, error.to_string()),
+        })?;
+
+    let req = req.data(scope).data(user);
+    Ok(schema.execute(req).await.into())
 }
 
 /// Serve the GraphQL playground for development
@@ -0,0 +1,41 @@
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use graphql::check_in_token::{self, CheckInTokens};
+use serde::Deserialize;
+use tracing::instrument;
+
+/// The query parameters for rendering a check-in code
+#[derive(Debug, Deserialize)]
+pub(crate) struct CodeParams {
+    /// The slug of the event the token was issued for
+    event: String,
+    /// The participant's signed check-in token
+    token: String,
+}
+
+/// Render a check-in token as a PNG QR code
+///
+/// This is what acceptance emails link to, since email clients block images embedded as data
+/// URIs. Only valid tokens are rendered, so it cannot be used to generate arbitrary codes.
+#[instrument(name = "check_in::code", skip_all)]
+pub(crate) async fn code(
+    State(tokens): State<CheckInTokens>,
+    Query(params): Query<CodeParams>,
+) -> Result<Response, StatusCode> {
+    if tokens.verify(&params.event, &params.token).is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let png = check_in_token::render_png(params.token.trim());
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, "private, max-age=86400"),
+        ],
+        png,
+    )
+        .into_response())
+}
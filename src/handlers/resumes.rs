@@ -0,0 +1,159 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use context::{Scope, User, UserRole};
+use database::{PgPool, Resume};
+use graphql::{resume_book::ResumeBookLinks, storage::Storage};
+use serde::Deserialize;
+use std::io::{self, BufWriter, Write};
+use tokio::{sync::mpsc, task};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{error, instrument, warn};
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+/// How much of the archive is buffered before it is sent to the client
+const CHUNK_SIZE: usize = 64 * 1024;
+/// How many chunks can be waiting to be sent before building the archive pauses
+const MAX_PENDING_CHUNKS: usize = 4;
+
+/// The query parameters for exporting the resume book
+#[derive(Debug, Deserialize)]
+pub(crate) struct BookParams {
+    /// A signed token issued to sponsors through `createResumeBookLink`
+    token: Option<String>,
+}
+
+/// Export the resumes that can be shared with sponsors as a zip archive
+///
+/// Organizers can export the resume book directly, while sponsors use a link with a signed token
+/// that an organizer issued for the event. It includes the resumes of accepted or checked in
+/// participants who opted into sharing their information. Each file is prefixed with the ID of the
+/// participant it belongs to.
+///
+/// The archive is streamed to the client as it is built, so only one resume is held in memory at
+/// a time.
+#[instrument(name = "resumes::book", skip_all)]
+pub(crate) async fn book(
+    State(db): State<PgPool>,
+    State(storage): State<Storage>,
+    State(links): State<ResumeBookLinks>,
+    scope: Scope,
+    user: User,
+    Query(params): Query<BookParams>,
+) -> Result<Response, StatusCode> {
+    let Scope::Event(scope) = scope else {
+        return Err(StatusCode::FORBIDDEN);
+    };
+    match (params.token, user) {
+        (Some(token), _) => {
+            if !links.verify(&scope.event, &token) {
+                return Err(StatusCode::FORBIDDEN);
+            }
+        }
+        (None, User::Authenticated(user)) => {
+            if !matches!(user.role, Some(UserRole::Organizer | UserRole::Director)) {
+                return Err(StatusCode::FORBIDDEN);
+            }
+        }
+        (None, User::Unauthenticated) => return Err(StatusCode::UNAUTHORIZED),
+    }
+
+    let resumes = Resume::shareable(&scope.event, &db)
+        .await
+        .map_err(|error| {
+            error!(%error, "failed to load shareable resumes");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let (tx, rx) = mpsc::channel(MAX_PENDING_CHUNKS);
+    tokio::spawn(async move {
+        if let Err(error) = write_archive(resumes, storage, tx.clone()).await {
+            error!(%error, "failed to build resume book");
+            let _ = tx.send(Err(error)).await;
+        }
+    });
+
+    let disposition = format!("attachment; filename=\"{}-resumes.zip\"", scope.event);
+    Ok((
+        [
+            (header::CONTENT_TYPE, String::from("application/zip")),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(ReceiverStream::new(rx)),
+    )
+        .into_response())
+}
+
+/// Build a zip archive from the resumes, sending it to the response body as it is written
+async fn write_archive(
+    resumes: Vec<Resume>,
+    storage: Storage,
+    tx: mpsc::Sender<io::Result<Bytes>>,
+) -> io::Result<()> {
+    let writer = BufWriter::with_capacity(CHUNK_SIZE, BodyWriter(tx));
+    let mut zip = ZipWriter::new_stream(writer);
+    let options = SimpleFileOptions::default();
+
+    for resume in resumes {
+        let content = match storage.get(&resume.key).await {
+            Ok(Some(content)) => content,
+            Ok(None) => {
+                warn!(key = resume.key, "resume is missing from storage");
+                continue;
+            }
+            Err(error) => {
+                error!(key = resume.key, %error, "failed to load resume");
+                return Err(io::Error::other("failed to load resume"));
+            }
+        };
+
+        let name = archive_name(&resume);
+        zip = task::spawn_blocking(move || {
+            zip.start_file(name, options).map_err(io::Error::other)?;
+            zip.write_all(&content)?;
+            Ok::<_, io::Error>(zip)
+        })
+        .await
+        .map_err(io::Error::other)??;
+    }
+
+    task::spawn_blocking(move || zip.finish().map_err(io::Error::other)?.flush())
+        .await
+        .map_err(io::Error::other)?
+}
+
+/// Sends everything written to it to the response body, waiting while the client catches up
+///
+/// Writes block the current thread, so it must only be used from blocking tasks.
+struct BodyWriter(mpsc::Sender<io::Result<Bytes>>);
+
+impl Write for BodyWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Get the name of a resume within the archive, keeping only characters that are safe in paths
+fn archive_name(resume: &Resume) -> String {
+    let filename = resume
+        .filename
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect::<String>();
+
+    format!("{}-{filename}", resume.participant_id)
+}
@@ -2,37 +2,268 @@ use axum::{
     extract::{FromRef, State},
     http::StatusCode,
     routing::post,
-    Json, Router,
+    Router,
+};
+use database::{
+    Application, ApplicationStatus, CheckIn, Checkpoint, CheckpointScan, DraftApplication, Email,
+    InboundWebhook, KioskSyncEntry, PgConnection, PgPool, PresenceEntry, Resume,
+};
+use graphql::{
+    check_in_token::CheckInTokens,
+    storage::Storage,
+    waitlist,
+    webhooks::{self, Deleted},
 };
-use database::{Email, PgPool};
 use serde::Deserialize;
-use tracing::{error, instrument};
+use tracing::{error, instrument, warn};
+
+mod signature;
+
+use signature::Signed;
+pub use signature::Verifier;
 
 /// Create the webhook router
+///
+/// Every webhook must be signed by the sender, otherwise it is rejected.
 pub fn router<S>() -> Router<S>
 where
     S: Clone + Send + Sync + 'static,
     PgPool: FromRef<S>,
+    CheckInTokens: FromRef<S>,
+    Storage: FromRef<S>,
+    Verifier: FromRef<S>,
 {
-    Router::new().route("/participant", post(participant))
+    Router::new()
+        .route("/identity", post(identity))
+        .route("/participant", post(participant))
+}
+
+/// The events sent by the identity service
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", content = "object")]
+enum IdentityEvent {
+    /// A participant's details changed
+    #[serde(rename = "participant.updated")]
+    ParticipantUpdated(Participant),
+    /// A participant was removed from an event, or deleted entirely when no event is given
+    #[serde(rename = "participant.deleted")]
+    ParticipantDeleted(DeletedParticipant),
+    /// An event was deleted
+    #[serde(rename = "event.deleted")]
+    EventDeleted(DeletedEvent),
 }
 
 #[derive(Debug, Deserialize)]
 struct Participant {
     id: i32,
+    first_name: String,
+    last_name: String,
     primary_email: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct DeletedParticipant {
+    id: i32,
+    event: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeletedEvent {
+    slug: String,
+}
+
+/// Handle the events sent by the identity service
+#[instrument(name = "webhooks::identity", skip_all, fields(id = webhook.id))]
+async fn identity(
+    State(db): State<PgPool>,
+    State(storage): State<Storage>,
+    State(tokens): State<CheckInTokens>,
+    webhook: Signed<IdentityEvent>,
+) -> StatusCode {
+    let result = match &webhook.payload {
+        IdentityEvent::ParticipantUpdated(participant) => Email::upsert(
+            participant.id,
+            &participant.primary_email,
+            &participant.first_name,
+            &participant.last_name,
+            &db,
+        )
+        .await
+        .map(|_| ()),
+        IdentityEvent::ParticipantDeleted(participant) => {
+            delete_participant(
+                &db,
+                &storage,
+                &tokens,
+                participant.id,
+                participant.event.as_deref(),
+            )
+            .await
+        }
+        IdentityEvent::EventDeleted(event) => delete_event(&db, &storage, &event.slug).await,
+    };
+
+    match result {
+        Ok(()) => StatusCode::NO_CONTENT,
+        Err(error) => {
+            error!(event = ?webhook.payload, %error, "failed to handle identity event");
+            forget(&webhook.id, &db).await;
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
 /// Ensure a participant's details are in sync
-#[instrument(name = "webhooks::participant", skip(db))]
-async fn participant(State(db): State<PgPool>, participant: Json<Participant>) -> StatusCode {
-    let result = Email::upsert(participant.id, &participant.primary_email, &db).await;
+///
+/// Superseded by the `participant.updated` event sent to `/identity`.
+#[instrument(name = "webhooks::participant", skip_all, fields(id = webhook.id))]
+async fn participant(State(db): State<PgPool>, webhook: Signed<Participant>) -> StatusCode {
+    let participant = &webhook.payload;
+    let result = Email::upsert(
+        participant.id,
+        &participant.primary_email,
+        &participant.first_name,
+        &participant.last_name,
+        &db,
+    )
+    .await;
 
     match result {
         Ok(_) => StatusCode::NO_CONTENT,
         Err(error) => {
             error!(participant.id, %error, "failed to update email");
+            forget(&webhook.id, &db).await;
             StatusCode::INTERNAL_SERVER_ERROR
         }
     }
 }
+
+/// Remove a participant from an event
+///
+/// When no event is provided, the participant is removed from every event and their email is
+/// forgotten. Any slots freed by accepted participants are filled from the waitlist.
+#[instrument(name = "webhooks::delete_participant", skip(db, storage, tokens))]
+async fn delete_participant(
+    db: &PgPool,
+    storage: &Storage,
+    tokens: &CheckInTokens,
+    id: i32,
+    event: Option<&str>,
+) -> Result<(), database::Error> {
+    let mut txn = db.begin().await?;
+
+    let events = match event {
+        Some(event) => vec![event.to_owned()],
+        None => Application::events_for_participant(id, &mut txn).await?,
+    };
+
+    let mut freed = Vec::new();
+    let mut resumes = Vec::new();
+    for event in &events {
+        let removed = remove_from_event(&mut txn, event, id).await?;
+        if removed.freed_slot {
+            freed.push(event);
+        }
+        resumes.extend(removed.resume);
+    }
+
+    if event.is_none() {
+        Email::delete(id, &mut txn).await?;
+    }
+
+    txn.commit().await?;
+    remove_resumes(storage, resumes).await;
+
+    for event in freed {
+        if let Err(error) = waitlist::promote(db, tokens, event, None).await {
+            error!(%event, %error, "failed to promote from waitlist");
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove every participant and checkpoint from a deleted event
+#[instrument(name = "webhooks::delete_event", skip(db, storage))]
+async fn delete_event(db: &PgPool, storage: &Storage, event: &str) -> Result<(), database::Error> {
+    let mut txn = db.begin().await?;
+
+    let mut resumes = Vec::new();
+    for id in Application::participants_for_event(event, &mut txn).await? {
+        let removed = remove_from_event(&mut txn, event, id).await?;
+        resumes.extend(removed.resume);
+    }
+    Checkpoint::delete_for_event(event, &mut txn).await?;
+
+    txn.commit().await?;
+    remove_resumes(storage, resumes).await;
+
+    Ok(())
+}
+
+/// What was removed along with a participant's application
+struct Removed {
+    /// Whether the participant held an accepted slot
+    freed_slot: bool,
+    /// Where the participant's resume was stored, if they uploaded one
+    resume: Option<String>,
+}
+
+/// Delete a participant's application, draft application, check in, scans, synced kiosk entries,
+/// and resume for an event
+async fn remove_from_event(
+    conn: &mut PgConnection,
+    event: &str,
+    id: i32,
+) -> Result<Removed, database::Error> {
+    let application = Application::find(event, id, &mut *conn).await?;
+
+    Application::delete(event, id, &mut *conn).await?;
+    DraftApplication::delete(event, id, &mut *conn).await?;
+    CheckIn::delete(event, id, &mut *conn).await?;
+    CheckpointScan::delete_for_participant(event, id, &mut *conn).await?;
+    PresenceEntry::delete_for_participant(event, id, &mut *conn).await?;
+    KioskSyncEntry::delete_for_participant(event, id, &mut *conn).await?;
+    let resume = Resume::delete(event, id, &mut *conn)
+        .await?
+        .map(|resume| resume.key);
+
+    let Some(application) = application else {
+        return Ok(Removed {
+            freed_slot: false,
+            resume,
+        });
+    };
+
+    webhooks::enqueue(
+        conn,
+        "application.deleted",
+        event,
+        &Deleted { participant_id: id },
+    )
+    .await?;
+
+    Ok(Removed {
+        freed_slot: matches!(
+            application.status,
+            ApplicationStatus::Accepted | ApplicationStatus::Confirmed
+        ),
+        resume,
+    })
+}
+
+/// Remove the files for deleted resumes once the deletion is committed
+async fn remove_resumes(storage: &Storage, keys: Vec<String>) {
+    for key in keys {
+        if let Err(error) = storage.delete(&key).await {
+            warn!(%key, %error, "failed to remove deleted resume");
+        }
+    }
+}
+
+/// Forget a webhook that could not be processed so the sender's retries are accepted
+async fn forget(id: &str, db: &PgPool) {
+    if let Err(error) = InboundWebhook::forget(id, db).await {
+        error!(%id, %error, "failed to forget webhook");
+    }
+}
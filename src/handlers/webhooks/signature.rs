@@ -0,0 +1,150 @@
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRef, FromRequest, Request},
+    http::{HeaderMap, StatusCode},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use database::{InboundWebhook, PgPool};
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use sha2::Sha256;
+use std::{
+    fmt::{Display, Formatter},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{error, warn};
+
+/// The prefix added to signing secrets
+const SECRET_PREFIX: &str = "whsec_";
+
+/// Verifies the Svix-format signatures of incoming webhooks
+#[derive(Clone)]
+pub struct Verifier {
+    key: Arc<[u8]>,
+    tolerance: Duration,
+}
+
+impl Verifier {
+    /// Create a verifier using the sender's signing secret
+    ///
+    /// Webhooks with a timestamp further than the tolerance from the current time are rejected.
+    pub fn new(secret: &str, tolerance: Duration) -> Result<Self, InvalidSecret> {
+        let key = STANDARD
+            .decode(secret.strip_prefix(SECRET_PREFIX).unwrap_or(secret))
+            .map_err(|_| InvalidSecret)?;
+        if key.is_empty() {
+            return Err(InvalidSecret);
+        }
+
+        Ok(Self {
+            key: key.into(),
+            tolerance,
+        })
+    }
+
+    /// Check the webhook's signature and timestamp, returning its ID if they are valid
+    fn verify<'h>(&self, headers: &'h HeaderMap, body: &[u8]) -> Result<&'h str, &'static str> {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .ok_or("missing signature headers")
+        };
+        let id = header("webhook-id")?;
+        let timestamp = header("webhook-timestamp")?;
+        let signatures = header("webhook-signature")?;
+
+        let sent_at = timestamp.parse::<i64>().map_err(|_| "invalid timestamp")?;
+        if Utc::now().timestamp().abs_diff(sent_at) > self.tolerance.as_secs() {
+            return Err("timestamp outside of tolerance");
+        }
+
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.key).expect("hmac accepts keys of any length");
+        mac.update(format!("{id}.{timestamp}.").as_bytes());
+        mac.update(body);
+
+        let valid = signatures
+            .split(' ')
+            .filter_map(|signature| signature.strip_prefix("v1,"))
+            .filter_map(|signature| STANDARD.decode(signature).ok())
+            .any(|signature| mac.clone().verify_slice(&signature).is_ok());
+        match valid {
+            true => Ok(id),
+            false => Err("no matching signature"),
+        }
+    }
+}
+
+/// The inbound webhook secret is not a non-empty base64-encoded key
+#[derive(Debug)]
+pub struct InvalidSecret;
+
+impl Display for InvalidSecret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "secret must be a non-empty base64-encoded key")
+    }
+}
+
+impl std::error::Error for InvalidSecret {}
+
+/// A webhook whose signature was verified and has not been received before
+pub(super) struct Signed<T> {
+    /// The sender's unique ID for the webhook
+    pub id: String,
+    /// The contents of the webhook
+    pub payload: T,
+}
+
+#[async_trait]
+impl<S, T> FromRequest<S> for Signed<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Send,
+    PgPool: FromRef<S>,
+    Verifier: FromRef<S>,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let verifier = Verifier::from_ref(state);
+        let db = PgPool::from_ref(state);
+
+        let headers = req.headers().clone();
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+        let id = match verifier.verify(&headers, &body) {
+            Ok(id) => id.to_owned(),
+            Err(reason) => {
+                warn!(reason, "rejected webhook with invalid signature");
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+        };
+
+        // Only record webhooks that can be handled, so that they can be retried after an upgrade
+        let payload = serde_json::from_slice(&body).map_err(|error| {
+            warn!(%id, %error, "received malformed webhook");
+            StatusCode::UNPROCESSABLE_ENTITY
+        })?;
+
+        let retention = verifier.tolerance.as_secs().saturating_mul(2) as i64;
+        match InboundWebhook::record(&id, retention, &db).await {
+            Ok(true) => {}
+            Ok(false) => {
+                warn!(%id, "rejected replayed webhook");
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+            Err(error) => {
+                error!(%id, %error, "failed to record webhook");
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+
+        Ok(Self { id, payload })
+    }
+}
@@ -0,0 +1,162 @@
+use database::{EventSettings, PgPool};
+use graphql::{check_in_token::CheckInTokens, webhooks};
+use std::{collections::HashSet, time::Duration};
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{error, info, instrument};
+
+/// The maximum number of emails to deliver in a single batch
+const EMAIL_BATCH_SIZE: i64 = 50;
+/// The maximum number of webhooks to publish in a single batch
+const WEBHOOK_BATCH_SIZE: i64 = 50;
+
+/// Periodically fill the remaining capacity of events from their waitlists
+pub fn waitlist_promotion(db: PgPool, tokens: CheckInTokens, period: Duration) {
+    tokio::task::spawn(async move {
+        let mut ticker = interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            promote_from_waitlists(&db, &tokens).await;
+        }
+    });
+}
+
+/// Promote applications for every event with a limited capacity
+#[instrument(name = "jobs::waitlist_promotion", skip_all)]
+async fn promote_from_waitlists(db: &PgPool, tokens: &CheckInTokens) {
+    let events = match EventSettings::with_capacity(db).await {
+        Ok(events) => events,
+        Err(error) => {
+            error!(%error, "failed to list events with a capacity");
+            return;
+        }
+    };
+
+    for event in events {
+        match graphql::waitlist::promote(db, tokens, &event, None).await {
+            Ok(promoted) if !promoted.is_empty() => {
+                info!(%event, count = promoted.len(), "promoted applications from waitlist")
+            }
+            Ok(_) => {}
+            Err(error) => error!(%event, %error, "failed to promote applications from waitlist"),
+        }
+    }
+}
+
+/// Periodically decline accepted applications that missed their RSVP deadline
+pub fn rsvp_expiration(db: PgPool, tokens: CheckInTokens, period: Duration) {
+    tokio::task::spawn(async move {
+        let mut ticker = interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            decline_expired_rsvps(&db, &tokens).await;
+        }
+    });
+}
+
+/// Decline expired RSVPs and refill the freed slots from the affected events' waitlists
+#[instrument(name = "jobs::rsvp_expiration", skip_all)]
+async fn decline_expired_rsvps(db: &PgPool, tokens: &CheckInTokens) {
+    let declined = match graphql::rsvp::decline_expired(db, tokens).await {
+        Ok(declined) => declined,
+        Err(error) => {
+            error!(%error, "failed to decline expired rsvps");
+            return;
+        }
+    };
+    if declined.is_empty() {
+        return;
+    }
+
+    info!(count = declined.len(), "declined expired rsvps");
+
+    let events = declined
+        .iter()
+        .map(|application| application.event.as_str())
+        .collect::<HashSet<_>>();
+    for event in events {
+        if let Err(error) = graphql::waitlist::promote(db, tokens, event, None).await {
+            error!(%event, %error, "failed to promote applications from waitlist");
+        }
+    }
+}
+
+/// Periodically deliver the emails queued in the outbox and any pending announcements
+pub fn email_delivery(db: PgPool, mail: mail::Client, period: Duration, max_attempts: i32) {
+    tokio::task::spawn(async move {
+        let mut ticker = interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            deliver_emails(&db, &mail, max_attempts).await;
+            deliver_announcements(&db, &mail).await;
+        }
+    });
+}
+
+/// Deliver all the emails that are currently due
+#[instrument(name = "jobs::email_delivery", skip(db, mail))]
+async fn deliver_emails(db: &PgPool, mail: &mail::Client, max_attempts: i32) {
+    loop {
+        match mail::outbox::deliver(mail, db, EMAIL_BATCH_SIZE, max_attempts).await {
+            Ok(attempted) if attempted as i64 == EMAIL_BATCH_SIZE => continue,
+            Ok(_) => break,
+            Err(error) => {
+                error!(%error, "failed to deliver emails");
+                break;
+            }
+        }
+    }
+}
+
+/// Deliver all the announcements that are waiting to be sent
+#[instrument(name = "jobs::announcement_delivery", skip_all)]
+async fn deliver_announcements(db: &PgPool, mail: &mail::Client) {
+    loop {
+        match mail::announcement::deliver(mail, db).await {
+            Ok(attempted) if attempted == mail::MAX_BATCH_SIZE => continue,
+            Ok(_) => break,
+            Err(error) => {
+                error!(%error, "failed to deliver announcements");
+                break;
+            }
+        }
+    }
+}
+
+/// Periodically publish the webhooks queued in the outbox
+pub fn webhook_delivery(
+    db: PgPool,
+    webhooks: webhooks::Client,
+    period: Duration,
+    max_attempts: i32,
+) {
+    tokio::task::spawn(async move {
+        let mut ticker = interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            publish_webhooks(&db, &webhooks, max_attempts).await;
+        }
+    });
+}
+
+/// Publish all the webhooks that are currently due
+#[instrument(name = "jobs::webhook_delivery", skip(db, webhooks))]
+async fn publish_webhooks(db: &PgPool, webhooks: &webhooks::Client, max_attempts: i32) {
+    loop {
+        match webhooks::publish(webhooks, db, WEBHOOK_BATCH_SIZE, max_attempts).await {
+            Ok(attempted) if attempted as i64 == WEBHOOK_BATCH_SIZE => continue,
+            Ok(_) => break,
+            Err(error) => {
+                error!(%error, "failed to publish webhooks");
+                break;
+            }
+        }
+    }
+}
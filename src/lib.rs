@@ -1,21 +1,45 @@
-use axum::{routing::get, Router};
+// Resolving the merged mutation type nests deeper than the default limit allows
+#![recursion_limit = "256"]
+
+use axum::{extract::DefaultBodyLimit, routing::get, Router};
 use database::PgPool;
-use svix::api::Svix;
 
 mod handlers;
+pub mod jobs;
 mod state;
 
+pub use handlers::Verifier;
 use state::AppState;
 
 /// Setup the routes
-pub fn router(db: PgPool, mail: mail::Client, svix: Svix) -> Router {
+pub fn router(
+    db: PgPool,
+    webhooks: graphql::webhooks::Client,
+    storage: graphql::storage::Storage,
+    tokens: graphql::check_in_token::CheckInTokens,
+    resume_book_links: graphql::resume_book::ResumeBookLinks,
+    kiosk_signer: graphql::kiosk_signer::KioskSigner,
+    verifier: Verifier,
+) -> Router {
     let router = Router::new()
         .route(
             "/graphql",
-            get(handlers::playground).post(handlers::graphql),
+            get(handlers::playground)
+                .post(handlers::graphql)
+                .layer(DefaultBodyLimit::max(graphql::MAX_REQUEST_SIZE)),
         )
+        .route("/check-in-code", get(handlers::check_in_code))
+        .route("/resume-book", get(handlers::resume_book))
         .nest("/webhooks", handlers::webhooks())
-        .with_state(AppState::new(db, mail, svix))
+        .with_state(AppState::new(
+            db,
+            webhooks,
+            storage,
+            tokens,
+            resume_book_links,
+            kiosk_signer,
+            verifier,
+        ))
         .layer(logging::http());
 
     Router::new()
@@ -1,10 +1,13 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use eyre::{eyre, WrapErr};
+use graphql::{
+    check_in_token::CheckInTokens, kiosk_signer::KioskSigner, resume_book::ResumeBookLinks,
+    storage, webhooks,
+};
 use logging::OpenTelemetryProtocol;
-use std::net::SocketAddr;
-use svix::api::Svix;
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 use tokio::{net::TcpListener, signal};
-use tracing::{info, Level};
+use tracing::{info, warn, Level};
 
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
@@ -20,10 +23,87 @@ async fn main() -> eyre::Result<()> {
     logging.init()?;
 
     let db = database::connect(&config.database_url).await?;
-    let mail = mail::Client::new(config.postmark_server_token);
-    let svix = Svix::new(config.svix_api_key, None);
+    let mail = match config.mail_transport {
+        MailTransport::Postmark => {
+            let token = config
+                .postmark_server_token
+                .ok_or_else(|| eyre!("a postmark server token is required"))?;
+            let postmark = mail::Postmark::new(token);
+            match postmark.missing_templates(mail::Template::ALIASES).await {
+                Ok(missing) if !missing.is_empty() => {
+                    return Err(eyre!(
+                        "postmark is missing templates: {}",
+                        missing.join(", ")
+                    ));
+                }
+                Ok(_) => {}
+                Err(error) => warn!(%error, "failed to check for missing postmark templates"),
+            }
 
-    let router = portal::router(db, mail, svix);
+            mail::Client::new(postmark)
+        }
+        MailTransport::Smtp => {
+            let url = config
+                .smtp_url
+                .ok_or_else(|| eyre!("an smtp url is required"))?;
+            let transport = mail::Smtp::new(&url).wrap_err("invalid smtp url")?;
+            mail::Client::new(transport)
+        }
+        MailTransport::Local => mail::Client::new(mail::Local::new(config.mail_directory)),
+    };
+    let webhooks = match config.webhook_sink {
+        WebhookSink::Svix => {
+            let api_key = config
+                .svix_api_key
+                .ok_or_else(|| eyre!("a svix api key is required"))?;
+            webhooks::Client::new(webhooks::SvixSink::new(api_key))
+        }
+        WebhookSink::Http => webhooks::Client::new(webhooks::HttpSink::new()),
+    };
+    let storage = storage::Storage::new(storage::LocalStorage::new(config.storage_directory));
+    let tokens = CheckInTokens::new(&config.check_in_token_secret, &config.public_url);
+    let resume_book_links = ResumeBookLinks::new(&config.resume_book_link_secret);
+    let kiosk_signer =
+        KioskSigner::new(&config.kiosk_signing_key).wrap_err("invalid kiosk signing key")?;
+
+    portal::jobs::email_delivery(
+        db.clone(),
+        mail,
+        Duration::from_secs(config.email_delivery_interval),
+        config.email_max_attempts,
+    );
+    let verifier = portal::Verifier::new(
+        &config.inbound_webhook_secret,
+        Duration::from_secs(config.inbound_webhook_tolerance),
+    )
+    .wrap_err("invalid inbound webhook secret")?;
+
+    portal::jobs::webhook_delivery(
+        db.clone(),
+        webhooks.clone(),
+        Duration::from_secs(config.webhook_delivery_interval),
+        config.webhook_max_attempts,
+    );
+    portal::jobs::waitlist_promotion(
+        db.clone(),
+        tokens.clone(),
+        Duration::from_secs(config.waitlist_promotion_interval),
+    );
+    portal::jobs::rsvp_expiration(
+        db.clone(),
+        tokens.clone(),
+        Duration::from_secs(config.rsvp_expiration_interval),
+    );
+
+    let router = portal::router(
+        db,
+        webhooks,
+        storage,
+        tokens,
+        resume_book_links,
+        kiosk_signer,
+        verifier,
+    );
 
     let listener = TcpListener::bind(&config.address)
         .await
@@ -69,17 +149,131 @@ struct Config {
     #[arg(long, default_value = "127.0.0.1:7878", env = "ADDRESS")]
     address: SocketAddr,
 
+    /// Where the portal can be reached from outside, used when linking to it from emails
+    #[arg(
+        long,
+        value_parser = clap::builder::NonEmptyStringValueParser::new(),
+        env = "PUBLIC_URL",
+    )]
+    public_url: String,
+
     /// The database to run migrations on
     #[arg(long, env = "DATABASE_URL")]
     database_url: String,
 
-    /// The Svix environment API key
+    /// Where webhooks should be published
+    #[arg(long, value_enum, default_value_t = WebhookSink::Svix, env = "WEBHOOK_SINK")]
+    webhook_sink: WebhookSink,
+
+    /// The Svix environment API key, required when using the svix sink
     #[arg(long, env = "SVIX_API_KEY")]
-    svix_api_key: String,
+    svix_api_key: Option<String>,
+
+    /// The secret used by the identity service to sign the webhooks it sends
+    #[arg(
+        long,
+        value_parser = clap::builder::NonEmptyStringValueParser::new(),
+        env = "INBOUND_WEBHOOK_SECRET",
+    )]
+    inbound_webhook_secret: String,
+
+    /// How far a received webhook's timestamp can be from the current time, in seconds
+    #[arg(long, default_value_t = 300, env = "INBOUND_WEBHOOK_TOLERANCE")]
+    inbound_webhook_tolerance: u64,
+
+    /// The secret used to sign the tokens participants check in with
+    #[arg(
+        long,
+        value_parser = clap::builder::NonEmptyStringValueParser::new(),
+        env = "CHECK_IN_TOKEN_SECRET",
+    )]
+    check_in_token_secret: String,
 
-    /// The Postmark server-scoped token
+    /// The secret used to sign the links sponsors download the resume book with
+    #[arg(
+        long,
+        value_parser = clap::builder::NonEmptyStringValueParser::new(),
+        env = "RESUME_BOOK_LINK_SECRET",
+    )]
+    resume_book_link_secret: String,
+
+    /// The base64-encoded 32-byte Ed25519 seed that kiosk snapshots are signed with
+    #[arg(long, env = "KIOSK_SIGNING_KEY")]
+    kiosk_signing_key: String,
+
+    /// Where uploaded files, like resumes, are stored
+    #[arg(long, default_value = "storage", env = "STORAGE_DIRECTORY")]
+    storage_directory: PathBuf,
+
+    /// How emails should be delivered
+    #[arg(long, value_enum, default_value_t = MailTransport::Postmark, env = "MAIL_TRANSPORT")]
+    mail_transport: MailTransport,
+
+    /// The Postmark server-scoped token, required when using the postmark transport
     #[arg(long, env = "POSTMARK_SERVER_TOKEN")]
-    postmark_server_token: String,
+    postmark_server_token: Option<String>,
+
+    /// The SMTP server to connect to, required when using the smtp transport
+    #[arg(long, env = "SMTP_URL")]
+    smtp_url: Option<String>,
+
+    /// Where to write emails to when using the local transport, they are only logged if unset
+    #[arg(long, env = "MAIL_DIRECTORY")]
+    mail_directory: Option<PathBuf>,
+
+    /// How often to deliver the queued emails, in seconds
+    #[arg(
+        long,
+        default_value_t = 5,
+        value_parser = clap::value_parser!(u64).range(1..),
+        env = "EMAIL_DELIVERY_INTERVAL",
+    )]
+    email_delivery_interval: u64,
+
+    /// How many times to attempt delivering an email before giving up
+    #[arg(
+        long,
+        default_value_t = 8,
+        value_parser = clap::value_parser!(i32).range(1..),
+        env = "EMAIL_MAX_ATTEMPTS",
+    )]
+    email_max_attempts: i32,
+
+    /// How often to publish the queued webhooks, in seconds
+    #[arg(
+        long,
+        default_value_t = 5,
+        value_parser = clap::value_parser!(u64).range(1..),
+        env = "WEBHOOK_DELIVERY_INTERVAL",
+    )]
+    webhook_delivery_interval: u64,
+
+    /// How many times to attempt publishing a webhook before giving up
+    #[arg(
+        long,
+        default_value_t = 8,
+        value_parser = clap::value_parser!(i32).range(1..),
+        env = "WEBHOOK_MAX_ATTEMPTS",
+    )]
+    webhook_max_attempts: i32,
+
+    /// How often to fill the remaining capacity of events from their waitlists, in seconds
+    #[arg(
+        long,
+        default_value_t = 60,
+        value_parser = clap::value_parser!(u64).range(1..),
+        env = "WAITLIST_PROMOTION_INTERVAL",
+    )]
+    waitlist_promotion_interval: u64,
+
+    /// How often to decline accepted applications that missed their RSVP deadline, in seconds
+    #[arg(
+        long,
+        default_value_t = 300,
+        value_parser = clap::value_parser!(u64).range(1..),
+        env = "RSVP_EXPIRATION_INTERVAL",
+    )]
+    rsvp_expiration_interval: u64,
 
     /// The default level to log at
     #[arg(long, default_value_t = Level::INFO, env = "LOG_LEVEL")]
@@ -99,6 +293,26 @@ struct Config {
     opentelemetry_protocol: OpenTelemetryProtocol,
 }
 
+/// The different methods of delivering emails
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum MailTransport {
+    /// Send using Postmark's templates
+    Postmark,
+    /// Send through an SMTP server
+    Smtp,
+    /// Log and optionally write to a local directory
+    Local,
+}
+
+/// The different destinations webhooks can be published to
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum WebhookSink {
+    /// Publish through Svix
+    Svix,
+    /// Deliver directly to the endpoints stored for each event
+    Http,
+}
+
 /// Load environment variables from a .env file, if it exists.
 fn dotenv() -> eyre::Result<()> {
     if let Err(error) = dotenvy::dotenv() {
@@ -1,5 +1,9 @@
+use crate::handlers::Verifier;
 use database::PgPool;
-use svix::api::Svix;
+use graphql::{
+    check_in_token::CheckInTokens, kiosk_signer::KioskSigner, resume_book::ResumeBookLinks,
+    storage::Storage,
+};
 
 macro_rules! state {
     ( $( $field:ident : $type:ty ),+ $(,)? ) => {
@@ -22,13 +26,36 @@ macro_rules! state {
 state! {
     db: PgPool,
     schema: graphql::Schema,
+    storage: Storage,
+    tokens: CheckInTokens,
+    resume_book_links: ResumeBookLinks,
+    verifier: Verifier,
 }
 
 impl AppState {
-    pub(crate) fn new(db: PgPool, mail: mail::Client, svix: Svix) -> Self {
+    pub(crate) fn new(
+        db: PgPool,
+        webhooks: graphql::webhooks::Client,
+        storage: Storage,
+        tokens: CheckInTokens,
+        resume_book_links: ResumeBookLinks,
+        kiosk_signer: KioskSigner,
+        verifier: Verifier,
+    ) -> Self {
         Self {
             db: db.clone(),
-            schema: graphql::schema(db, mail, svix),
+            schema: graphql::schema(
+                db,
+                webhooks,
+                storage.clone(),
+                tokens.clone(),
+                resume_book_links.clone(),
+                kiosk_signer,
+            ),
+            storage,
+            tokens,
+            resume_book_links,
+            verifier,
         }
     }
 }
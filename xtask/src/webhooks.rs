@@ -1,4 +1,6 @@
+use database::ApplicationStatus;
 use eyre::{eyre, WrapErr};
+use graphql::StatusChanged;
 use schemars::gen::SchemaSettings;
 use std::{fs::OpenOptions, io::Write, path::PathBuf};
 use tracing::info;
@@ -21,12 +23,54 @@ pub fn run(args: Args) -> eyre::Result<()> {
         .into_generator();
 
     let application = examples::application();
-    let schema = openapi::generate(&[Webhook::new(
-        &mut generator,
-        "application.submitted",
-        "Published when a participant submits an application to your event.",
-        &application,
-    )]);
+    let accepted_application = examples::accepted_application();
+    let schema = openapi::generate(&[
+        Webhook::new(
+            &mut generator,
+            "application.submitted",
+            "Published when a participant submits an application to your event.",
+            &application,
+        ),
+        Webhook::new(
+            &mut generator,
+            "application.status_changed",
+            "Published when an application's status changes, including when participants RSVP.",
+            &StatusChanged {
+                previous_status: ApplicationStatus::Pending,
+                application: &accepted_application,
+            },
+        ),
+        Webhook::new(
+            &mut generator,
+            "application.updated",
+            "Published when an application is flagged, annotated, or edited by its participant.",
+            &examples::updated_application(),
+        ),
+        Webhook::new(
+            &mut generator,
+            "application.deleted",
+            "Published when an application to your event is deleted.",
+            &examples::deleted(),
+        ),
+        Webhook::new(
+            &mut generator,
+            "participant.checked_in",
+            "Published when a participant checks in to your event.",
+            &examples::check_in(),
+        ),
+        Webhook::new(
+            &mut generator,
+            "participant.checked_out",
+            "Published when a participant leaves your event.",
+            &examples::check_out(),
+        ),
+        Webhook::new(
+            &mut generator,
+            "draft_application.started",
+            "Published when a participant starts an application to your event.",
+            &examples::draft_application(),
+        ),
+    ]);
 
     let mut output = OpenOptions::new()
         .write(true)
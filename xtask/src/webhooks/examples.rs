@@ -1,6 +1,8 @@
-use chrono::{DateTime, Days, NaiveDate, Utc};
-use database::{Application, ApplicationStatus, Education, Gender, RaceEthnicity};
-use graphql::Payload;
+use chrono::{DateTime, Days, Duration, NaiveDate, Utc};
+use database::{
+    Application, ApplicationStatus, CheckIn, DraftApplication, Education, Gender, RaceEthnicity,
+};
+use graphql::{Deleted, Payload};
 use uuid::Uuid;
 
 const SCHOOL_ID: Uuid = Uuid::from_fields(
@@ -43,7 +45,7 @@ pub fn application() -> Application {
         locality: Some(String::from("Vancouver")),
         administrative_area: Some(String::from("British Columbia")),
         postal_code: String::from("V6C 3T4"),
-        country: String::from("Canada"),
+        country: String::from("CA"),
         share_information: true,
         status: ApplicationStatus::Pending,
         flagged: false,
@@ -52,3 +54,68 @@ pub fn application() -> Application {
         updated_at: DATE_TIME,
     }
 }
+
+pub fn accepted_application() -> Application {
+    Application {
+        status: ApplicationStatus::Accepted,
+        updated_at: DATE_TIME,
+        ..application()
+    }
+}
+
+pub fn updated_application() -> Application {
+    Application {
+        flagged: true,
+        notes: String::from("Double-check their graduation year"),
+        ..application()
+    }
+}
+
+pub fn deleted() -> Deleted {
+    Deleted { participant_id: 3 }
+}
+
+pub fn check_in() -> CheckIn {
+    CheckIn {
+        event: String::from("wafflehacks-2024"),
+        participant_id: 3,
+        at: DATE_TIME,
+        checked_out_at: None,
+    }
+}
+
+pub fn check_out() -> CheckIn {
+    CheckIn {
+        checked_out_at: Some(DATE_TIME + Duration::hours(6)),
+        ..check_in()
+    }
+}
+
+pub fn draft_application() -> DraftApplication {
+    DraftApplication {
+        event: String::from("wafflehacks-2024"),
+        participant_id: 3,
+        gender: Some(Gender::NonBinary),
+        race_ethnicity: None,
+        date_of_birth: None,
+        referrer: None,
+        school_id: Some(SCHOOL_ID),
+        education: None,
+        graduation_year: None,
+        major: None,
+        hackathons_attended: None,
+        vcs_url: None,
+        portfolio_url: None,
+        devpost_url: None,
+        address_line1: None,
+        address_line2: None,
+        address_line3: None,
+        locality: None,
+        administrative_area: None,
+        postal_code: None,
+        country: None,
+        share_information: false,
+        created_at: DATE_TIME,
+        updated_at: DATE_TIME,
+    }
+}